@@ -37,7 +37,9 @@ fn run(module: &'static UniversalModule) -> Result<(), SmallError> {
     )?;
 
     vm.initialize(module.bytecode().iter().copied())?;
-    vm.run()
+    vm.run()?;
+
+    Ok(())
 }
 
 fn stak(criterion: &mut Criterion) {
@@ -25,7 +25,8 @@ mod error;
 use alloc::sync::Arc;
 pub use error::BuildError;
 use glob::{glob, Paths};
-use stak_compiler::compile_r7rs;
+use stak_compiler::{compile as compile_with_options, source_marker};
+pub use stak_compiler::{CompileOptions, Prelude};
 use std::{
     env,
     ffi::OsStr,
@@ -47,15 +48,38 @@ use which::which;
 /// under the `src` directory. The resulting bytecode files are stored under the
 /// `target` directory.
 pub fn build_r7rs() -> Result<(), BuildError> {
+    build_with_options(CompileOptions::new())
+}
+
+/// Builds Scheme source files into bytecode files with compile options.
+///
+/// This function is the same as [`build_r7rs`] except that it compiles source
+/// files with given options. Source names in the options are overridden by
+/// paths of source files.
+///
+/// # Examples
+///
+/// ```rust no_run
+/// use stak_build::{build_with_options, BuildError, CompileOptions};
+///
+/// fn main() -> Result<(), BuildError> {
+///     build_with_options(
+///         CompileOptions::new()
+///             .with_heap_size(1 << 23)
+///             .with_debug_info(true),
+///     )
+/// }
+/// ```
+pub fn build_with_options(options: CompileOptions) -> Result<(), BuildError> {
     let runtime = Runtime::new()?;
     let _ = runtime.enter();
 
-    runtime.block_on(build(glob("**/*.scm")?))?;
+    runtime.block_on(build(glob("**/*.scm")?, Arc::new(options)))?;
 
     Ok(())
 }
 
-async fn build(paths: Paths) -> Result<(), BuildError> {
+async fn build(paths: Paths, options: Arc<CompileOptions>) -> Result<(), BuildError> {
     let compiler = which("stak-compile").ok().map(Arc::new);
 
     if compiler.is_none() {
@@ -87,7 +111,12 @@ async fn build(paths: Paths) -> Result<(), BuildError> {
         println!("cargo::rerun-if-changed={}", path.display());
 
         let out_path = out_directory.join(&path);
-        handles.push(spawn(compile(path, out_path, compiler.clone())))
+        handles.push(spawn(compile(
+            path,
+            out_path,
+            compiler.clone(),
+            options.clone(),
+        )))
     }
 
     for handle in handles {
@@ -101,18 +130,38 @@ async fn compile(
     src_path: PathBuf,
     out_path: PathBuf,
     compiler: Option<Arc<PathBuf>>,
+    options: Arc<CompileOptions>,
 ) -> Result<(), BuildError> {
     let mut buffer = vec![];
+    let name = src_path.display().to_string();
 
     if let Some(path) = compiler {
-        let mut command = Command::new(&*path)
+        let mut command = Command::new(&*path);
+
+        command
+            .arg("--heap-size")
+            .arg(options.heap_size().to_string());
+
+        if options.debug_info() {
+            command.arg("--debug-info");
+        }
+
+        if options.locations() {
+            command.arg("--locations");
+        }
+
+        let mut command = command
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .spawn()?;
         let stdin = command.stdin.as_mut().expect("stdin");
 
         stdin
-            .write_all(include_str!("prelude.scm").as_bytes())
+            .write_all(options.prelude().source().as_bytes())
+            .await?;
+        // Reset source positions after a prelude.
+        stdin
+            .write_all(source_marker(Some(&name)).as_bytes())
             .await?;
         stdin.write_all(&read(src_path).await?).await?;
 
@@ -124,7 +173,11 @@ async fn compile(
             .read_to_end(&mut buffer)
             .await?;
     } else {
-        compile_r7rs(&*read(&src_path).await?, &mut buffer)?;
+        compile_with_options(
+            &*read(&src_path).await?,
+            &mut buffer,
+            &options.as_ref().clone().with_name(name),
+        )?;
     }
 
     if let Some(path) = out_path.parent() {
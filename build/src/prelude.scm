@@ -1 +0,0 @@
-../../prelude.scm
\ No newline at end of file
@@ -6,5 +6,23 @@
 //! ```sh
 //! stak-compile < foo.scm > foo.bc
 //! ```
+//!
+//! With the `--prelude` option, it reads a prelude file before a source file.
+//! The `--heap-size` option sets a heap size of the compiler.
+//!
+//! ```sh
+//! stak-compile --prelude prelude.scm --heap-size 8388608 < foo.scm > foo.bc
+//! ```
+//!
+//! With the `--debug-info` flag, it records source positions of procedures and
+//! call sites in bytecodes. A `($$source "foo.scm")` form in source codes
+//! resets the positions to the beginning of a file of the given name.
+//!
+//! With the `--locations` flag, it prints source positions of offending data
+//! in errors and warnings.
+//!
+//! It prints warnings of references to undefined global variables and calls of
+//! procedures with wrong numbers of arguments to stderr without failing. The
+//! `--no-warnings` flag disables them.
 
 stak_sac::main!("main.scm");
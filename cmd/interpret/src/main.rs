@@ -5,6 +5,11 @@
 //! ```sh
 //! stak-interpret foo.bc
 //! ```
+//!
+//! On runtime errors, it prints a backtrace of procedure calls.
+//!
+//! Bytecode files with incompatible headers are rejected. If a fingerprint is
+//! given, it must also match one in a bytecode file.
 
 use clap::Parser;
 use main_error::MainError;
@@ -14,7 +19,7 @@ use stak_file::OsFileSystem;
 use stak_process_context::OsProcessContext;
 use stak_r7rs::SmallPrimitiveSet;
 use stak_time::OsClock;
-use stak_vm::Vm;
+use stak_vm::{Fingerprint, Vm};
 use std::{fs::read, path::PathBuf};
 
 #[derive(clap::Parser)]
@@ -26,6 +31,8 @@ struct Arguments {
     arguments: Vec<String>,
     #[arg(short = 's', long, default_value_t = DEFAULT_HEAP_SIZE)]
     heap_size: usize,
+    #[arg(short = 'f', long, value_parser = parse_fingerprint)]
+    fingerprint: Option<Fingerprint>,
 }
 
 fn main() -> Result<(), MainError> {
@@ -42,7 +49,34 @@ fn main() -> Result<(), MainError> {
         ),
     )?;
 
+    if let Some(fingerprint) = arguments.fingerprint {
+        vm = vm.with_fingerprint(fingerprint);
+    }
+
     vm.initialize(read(&arguments.file)?)?;
 
-    Ok(vm.run()?)
+    vm.run().map_err(|error| {
+        vm.backtrace().fold(error.to_string(), |message, frame| {
+            format!("{message}\nat {frame}")
+        })
+    })?;
+
+    Ok(())
+}
+
+fn parse_fingerprint(string: &str) -> Result<Fingerprint, String> {
+    let mut fingerprint = Fingerprint::default();
+
+    if string.len() != 2 * fingerprint.len() {
+        return Err("fingerprint must be 16 hexadecimal digits".into());
+    }
+
+    for (index, byte) in fingerprint.iter_mut().enumerate() {
+        *byte = string
+            .get(2 * index..2 * index + 2)
+            .ok_or("invalid fingerprint")
+            .and_then(|digits| u8::from_str_radix(digits, 16).map_err(|_| "invalid fingerprint"))?;
+    }
+
+    Ok(fingerprint)
 }
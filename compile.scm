@@ -4,7 +4,9 @@
 
 (import
   (scheme base)
+  (only (scheme char) char-whitespace?)
   (scheme cxr)
+  (scheme file)
   (scheme inexact)
   (scheme lazy)
   (scheme process-context)
@@ -63,6 +65,8 @@
 (define char-type 6)
 (define vector-type 7)
 (define bytevector-type 8)
+(define bignum-type 11)
+(define rational-type 12)
 
 ; Utility
 
@@ -242,19 +246,327 @@
 (define (id->string id)
   (number->string id 32))
 
+; Source positions
+
+; A position is a list of a file name, a line, and a column.
+;
+; We index positions by heads of expressions so that we do not search all
+; positions on every lookup.
+(define-record-type position-table
+  (make-position-table buckets)
+  position-table?
+  (buckets position-table-buckets position-table-set-buckets!))
+
+(define (position-table-ref table expression)
+  (let ((pair
+          (and
+            table
+            (pair? expression)
+            (let ((bucket (assq (car expression) (position-table-buckets table))))
+              (and bucket (assq expression (cdr bucket)))))))
+    (and pair (cdr pair))))
+
+(define (position-table-set! table expression position)
+  (when (and table (pair? expression) position)
+    (let* ((key (car expression))
+           (bucket
+             (or
+               (assq key (position-table-buckets table))
+               (let ((bucket (list key)))
+                 (position-table-set-buckets!
+                   table
+                   (cons bucket (position-table-buckets table)))
+                 bucket))))
+      (set-cdr! bucket (cons (cons expression position) (cdr bucket))))))
+
+; Lets a new expression inherit a position from an old one unless it has its own.
+(define (position-table-inherit! table old new)
+  (let ((position (position-table-ref table old)))
+    (when (and position (not (position-table-ref table new)))
+      (position-table-set! table new position)))
+  new)
+
+(define (position-table-deep-map table f xs)
+  (if (pair? xs)
+    (position-table-inherit!
+      table
+      xs
+      (cons
+        (position-table-deep-map table f (car xs))
+        (position-table-deep-map table f (cdr xs))))
+    (f xs)))
+
 ; Source code reading
 
-(define (read-all)
-  (let ((x (read)))
-    (if (eof-object? x)
-      '()
-      (cons x (read-all)))))
+;; Ports
+
+; We track source positions only in the compiler's reader so that ports in
+; programs stay lightweight. Columns count characters.
+(define-record-type source-port
+  (make-source-port port line column)
+  source-port?
+  (port source-port-port)
+  (line source-port-line source-port-set-line!)
+  (column source-port-column source-port-set-column!))
+
+(define (source-port-peek-char port)
+  (peek-char (source-port-port port)))
+
+(define (source-port-read-char port)
+  (let ((char (read-char (source-port-port port))))
+    (cond
+      ((eqv? char #\newline)
+        (source-port-set-line! port (+ (source-port-line port) 1))
+        (source-port-set-column! port 1))
+
+      ((char? char)
+        (source-port-set-column! port (+ (source-port-column port) 1))))
+    char))
+
+;; Reader
+
+(define special-chars
+  '(("alarm" . #\alarm)
+    ("backspace" . #\backspace)
+    ("delete" . #\delete)
+    ("escape" . #\escape)
+    ("newline" . #\newline)
+    ("null" . #\null)
+    ("return" . #\return)
+    ("space" . #\space)
+    ("tab" . #\tab)))
+
+(define (whitespace-char? char)
+  (and (char? char) (char-whitespace? char)))
+
+; A reader of the same syntax as `read` in the prelude. It calls a `record!`
+; procedure with lists and their lines and columns. We use it only to record
+; source positions and `read` otherwise.
+(define (read-datum port record!)
+  (define (peek-char)
+    (source-port-peek-char port))
+
+  (define (read-char)
+    (source-port-read-char port))
+
+  (define (read-raw)
+    (let ((char (peek-non-whitespace-char)))
+      (cond
+        ((eof-object? char)
+          char)
+
+        ((eqv? char #\()
+          (let* ((line (source-port-line port))
+                 (column (source-port-column port))
+                 (x (read-list)))
+            (when (pair? x)
+              (record! x line column))
+            x))
+
+        ((eqv? char #\#)
+          (read-char)
+          (case (peek-char)
+            ((#\f)
+              (read-char)
+              #f)
+
+            ((#\t)
+              (read-char)
+              #t)
+
+            ((#\\)
+              (read-char)
+              (let ((char (peek-char)))
+                (if (whitespace-char? char)
+                  (read-char)
+                  (let ((x (read-symbol-chars)))
+                    (cond
+                      ((null? x)
+                        (read-char))
+
+                      ((eq? (length x) 1)
+                        (car x))
+
+                      (else
+                        (cdr (assoc (list->string x) special-chars))))))))
+
+            ((#\u)
+              (read-char)
+              (read-char)
+              (apply bytevector (read-list)))
+
+            (else
+              (list->vector (read-list)))))
+
+        ((eqv? char #\')
+          (read-char)
+          (list 'quote (read-raw)))
+
+        ((eqv? char #\`)
+          (read-char)
+          (list 'quasiquote (read-raw)))
+
+        ((eqv? char #\,)
+          (read-char)
+          (if (eqv? (peek-char) #\@)
+            (begin
+              (read-char)
+              (list 'unquote-splicing (read-raw)))
+            (list 'unquote (read-raw))))
+
+        ((eqv? char #\")
+          (read-string))
+
+        (else
+          (let ((x (list->string (read-symbol-chars))))
+            (or (string->number x) (string->symbol x)))))))
+
+  (define (read-list)
+    (define (read-tail)
+      (let ((char (peek-non-whitespace-char)))
+        (cond
+          ((eof-object? char)
+            (error "unexpected end of input instead of closing parenthesis"))
+
+          ((eqv? char #\))
+            (read-char)
+            '())
+
+          (else
+            (let ((x (read-raw)))
+              (if (and (symbol? x) (equal? (symbol->string x) "."))
+                (let ((x (read-raw)))
+                  (read-char)
+                  x)
+                (cons x (read-tail))))))))
+
+    (unless (eqv? (read-char) #\()
+      (error "( expected"))
+    (read-tail))
+
+  (define (read-symbol-chars)
+    (let ((char (peek-char)))
+      (if (or
+           (memv char '(#\( #\)))
+           (eof-object? char)
+           (whitespace-char? char))
+        '()
+        (cons (read-char) (read-symbol-chars)))))
+
+  (define (read-string)
+    (unless (eqv? (read-char) #\")
+      (error "\" expected"))
+    (let loop ((xs '()))
+      (let ((char (read-char)))
+        (cond
+          ((eof-object? char)
+            (error "unexpected end of input instead of closing double quote"))
+
+          ((eqv? char #\")
+            (list->string (reverse xs)))
+
+          ((eqv? char #\\)
+            (let ((char (read-char)))
+              (loop
+                (cons
+                  (case char
+                    ((#\n)
+                      #\newline)
+
+                    ((#\r)
+                      #\return)
+
+                    ((#\t)
+                      #\tab)
+
+                    (else
+                      char))
+                  xs))))
+
+          (else
+            (loop (cons char xs)))))))
+
+  (define (peek-non-whitespace-char)
+    (let ((char (peek-char)))
+      (cond
+        ((whitespace-char? char)
+          (read-char)
+          (peek-non-whitespace-char))
+
+        ((eqv? char #\;)
+          (skip-comment))
+
+        (else
+          char))))
+
+  (define (skip-comment)
+    (let ((char (read-char)))
+      (cond
+        ((eof-object? char)
+          char)
+
+        ((eqv? char #\newline)
+          (peek-non-whitespace-char))
+
+        (else
+          (skip-comment)))))
+
+  (read-raw))
 
-(define (read-source)
+;; Sources
+
+; A `($$source name)` form resets source positions to the beginning of a file
+; of the name. A name is optional.
+(define (read-port port file record!)
+  (define source-port (and record! (make-source-port port 1 1)))
+  (define base-line 1)
+  (define base-column 1)
+
+  (define (record-position! expression line column)
+    (record!
+      expression
+      (list
+        file
+        (+ (- line base-line) 1)
+        (if (= line base-line)
+          (+ (- column base-column) 1)
+          column))))
+
+  (define (read-all)
+    (let ((x
+            (if source-port
+              (read-datum source-port record-position!)
+              (read port))))
+      (cond
+        ((eof-object? x)
+          '())
+
+        ((eq? (predicate x) '$$source)
+          (set! file (and (pair? (cdr x)) (cadr x)))
+          (when source-port
+            (set! base-line (source-port-line source-port))
+            (set! base-column (source-port-column source-port)))
+          (read-all))
+
+        (else
+          (cons x (read-all))))))
+
+  (read-all))
+
+; A prelude is a file read before a source.
+(define (read-source prelude record!)
   (cons
     '$$begin
     ; Keep an invariant that a `begin` body must not be empty.
-    (cons #f (read-all))))
+    (cons
+      #f
+      (append
+        (if prelude
+          (call-with-input-file prelude
+            (lambda (port)
+              (read-port port prelude record!)))
+          '())
+        (read-port (current-input-port) #f record!)))))
 
 ; Library system
 
@@ -277,10 +589,11 @@
   (imported library-state-imported library-state-set-imported!))
 
 (define-record-type library-context
-  (make-library-context libraries name-maps)
+  (make-library-context libraries name-maps positions)
   library-context?
   (libraries library-context-libraries library-context-set-libraries!)
-  (name-maps library-context-name-maps library-context-set-name-maps!))
+  (name-maps library-context-name-maps library-context-set-name-maps!)
+  (positions library-context-positions))
 
 (define (library-context-assoc context name)
   (cond
@@ -454,7 +767,8 @@
                   (cons name (rename-library-symbol context id name))))
               exports)
             (collect-bodies 'import)
-            (relaxed-deep-map
+            (position-table-deep-map
+              (library-context-positions context)
               (lambda (value)
                 (if (symbol? value)
                   (rename-library-symbol context id value)
@@ -471,8 +785,8 @@
 
 (define library-predicates '(define-library import))
 
-(define (expand-libraries expression)
-  (let* ((context (make-library-context '() '()))
+(define (expand-libraries expression positions)
+  (let* ((context (make-library-context '() '() positions))
          (body-symbols
            (delay
              (deep-unique
@@ -498,10 +812,11 @@
 ;; Types
 
 (define-record-type macro-state
-  (make-macro-state id literals)
+  (make-macro-state id literals positions)
   macro-state?
   (id macro-state-id macro-state-set-id!)
-  (literals macro-state-literals macro-state-set-literals!))
+  (literals macro-state-literals macro-state-set-literals!)
+  (positions macro-state-positions))
 
 (define-record-type macro-context
   (make-macro-context state environment)
@@ -528,6 +843,9 @@
         (macro-context-set-environment! context tail)
         (set-last-cdr! environment tail)))))
 
+(define (macro-context-inherit-position! context old new)
+  (position-table-inherit! (macro-state-positions (macro-context-state context)) old new))
+
 (define (macro-context-generate-id! context)
   (let* ((state (macro-context-state context))
          (id (macro-state-id state)))
@@ -788,7 +1106,11 @@
         (($$define)
           (let ((name (cadr expression)))
             (macro-context-set! context name name)
-            (expand (cons '$$set! (cdr expression)))))
+            (expand
+              (macro-context-inherit-position!
+                context
+                expression
+                (cons '$$set! (cdr expression))))))
 
         (($$define-syntax)
           (macro-context-set-last!
@@ -814,10 +1136,13 @@
                    (relaxed-deep-map
                      (lambda (name) (resolve-denotation context name))
                      parameters)))
-            (list
-              '$$lambda
-              parameters
-              (expand-macro context (caddr expression)))))
+            (macro-context-inherit-position!
+              context
+              expression
+              (list
+                '$$lambda
+                parameters
+                (expand-macro context (caddr expression))))))
 
         (($$let-syntax)
           (expand-macro
@@ -859,15 +1184,17 @@
         (else =>
           (lambda (value)
             (if (procedure? value)
-              (let-values (((expression context) (value context expression)))
-                (expand-macro context expression))
-              (map expand expression))))))
+              (let-values (((new-expression context) (value context expression)))
+                (expand-macro
+                  context
+                  (macro-context-inherit-position! context expression new-expression)))
+              (macro-context-inherit-position! context expression (map expand expression)))))))
 
     (else
       expression)))
 
-(define (expand-macros expression)
-  (let* ((context (make-macro-context (make-macro-state 0 '()) '()))
+(define (expand-macros expression positions)
+  (let* ((context (make-macro-context (make-macro-state 0 '() positions) '()))
          (expression (expand-macro context expression)))
     (values
       expression
@@ -879,10 +1206,14 @@
 ; Optimization
 
 (define-record-type optimization-context
-  (make-optimization-context optimizers literals)
+  (make-optimization-context optimizers literals positions)
   optimization-context?
   (optimizers optimization-context-optimizers optimization-context-set-optimizers!)
-  (literals optimization-context-literals optimization-context-set-literals!))
+  (literals optimization-context-literals optimization-context-set-literals!)
+  (positions optimization-context-positions))
+
+(define (optimization-context-inherit-position! context old new)
+  (position-table-inherit! (optimization-context-positions context) old new))
 
 (define (optimization-context-append! context name optimizer)
   (optimization-context-set-optimizers!
@@ -950,10 +1281,13 @@
 (define (optimize-expression context expression)
   (if (pair? expression)
     (let* ((expression
-             (relaxed-map
-               (lambda (expression)
-                 (optimize-expression context expression))
-               expression))
+             (optimization-context-inherit-position!
+               context
+               expression
+               (relaxed-map
+                 (lambda (expression)
+                   (optimize-expression context expression))
+                 expression)))
            (predicate (car expression)))
       (cond
         ((eq? predicate '$$define-optimizer)
@@ -981,29 +1315,120 @@
 
         ((assq predicate (optimization-context-optimizers context)) =>
           (lambda (pair)
-            ((cdr pair) expression)))
+            (optimization-context-inherit-position!
+              context
+              expression
+              ((cdr pair) expression))))
 
         (else
           expression)))
     expression))
 
-(define (optimize expression)
-  (let* ((context (make-optimization-context '() '()))
+(define (optimize expression positions)
+  (let* ((context (make-optimization-context '() '() positions))
          (expression (optimize-expression context expression)))
     (values expression (optimization-context-literals context))))
 
+; Checking
+
+; We check expressions after macro expansion for possible mistakes in programs.
+; Local variables are renamed into unique symbols on macro expansion and
+; never conflict with global ones.
+;
+; Note that `$$define-optimizer` must not be at the head of a list literal here
+; as the optimizer would treat it as a definition of an optimizer.
+
+; Global variables assigned only once with `lambda` expressions are procedures
+; with known arities.
+(define (find-global-definitions expression)
+  (define definitions '())
+
+  (let loop ((expression expression))
+    (when (pair? expression)
+      (case (car expression)
+        (($$quote $$define-optimizer)
+          #f)
+
+        (($$set!)
+          (let ((pair (assq (cadr expression) definitions)))
+            (if pair
+              (set-cdr! pair #f)
+              (set! definitions (cons (cons (cadr expression) (caddr expression)) definitions))))
+          (loop (caddr expression)))
+
+        (($$lambda)
+          (loop (caddr expression)))
+
+        (else
+          (for-each loop expression)))))
+
+  definitions)
+
+; Expressions are a list of enclosing expressions from the innermost one.
+(define (check-expression warn expression)
+  (define definitions (find-global-definitions expression))
+
+  (define (check-variable name expressions)
+    (unless (or (assq name definitions) (assq name primitives))
+      (warn expressions "undefined global variable" name)))
+
+  (define (check-arity call expressions)
+    (let* ((pair (assq (car call) definitions))
+           (procedure (and pair (cdr pair))))
+      (when (and (pair? procedure) (eq? (car procedure) '$$lambda))
+        (let ((parameters (cadr procedure))
+              (count (length (cdr call))))
+          (unless ((if (symbol? (last-cdr parameters)) >= =)
+                   count
+                   (count-parameters parameters))
+            (warn expressions "wrong number of arguments" call))))))
+
+  (let check ((locals '()) (expressions '()) (expression expression))
+    (define (check-all expressions* xs)
+      (for-each (lambda (x) (check locals expressions* x)) xs))
+
+    (cond
+      ((symbol? expression)
+        (unless (memq expression locals)
+          (check-variable expression expressions)))
+
+      ((pair? expression)
+        (let ((expressions (cons expression expressions)))
+          (case (car expression)
+            (($$libraries $$macros $$optimizers $$quote $$symbols $$define-optimizer)
+              #f)
+
+            (($$apply $$begin $$if)
+              (check-all expressions (cdr expression)))
+
+            (($$lambda)
+              (check
+                (append (parameter-names (cadr expression)) locals)
+                expressions
+                (caddr expression)))
+
+            (($$set!)
+              (check locals expressions (caddr expression)))
+
+            (else
+              (when (and (symbol? (car expression)) (not (memq (car expression) locals)))
+                (check-arity expression expressions))
+              (check-all expressions expression))))))))
+
 ; Compilation
 
 ;; Context
 
 (define-record-type compilation-context
-  (make-compilation-context environment symbols libraries macros optimizers)
+  (make-compilation-context environment symbols libraries macros optimizers positions position)
   compilation-context?
   (environment compilation-context-environment)
   (symbols compilation-context-symbols)
   (libraries compilation-context-libraries)
   (macros compilation-context-macros)
-  (optimizers compilation-context-optimizers))
+  (optimizers compilation-context-optimizers)
+  (positions compilation-context-positions)
+  (position compilation-context-position))
 
 (define (compilation-context-append-locals context variables)
   (make-compilation-context
@@ -1011,7 +1436,24 @@
     (compilation-context-symbols context)
     (compilation-context-libraries context)
     (compilation-context-macros context)
-    (compilation-context-optimizers context)))
+    (compilation-context-optimizers context)
+    (compilation-context-positions context)
+    (compilation-context-position context)))
+
+; Expressions without their own positions are at the ones of their enclosing
+; expressions.
+(define (compilation-context-enter context expression)
+  (let ((position (position-table-ref (compilation-context-positions context) expression)))
+    (if position
+      (make-compilation-context
+        (compilation-context-environment context)
+        (compilation-context-symbols context)
+        (compilation-context-libraries context)
+        (compilation-context-macros context)
+        (compilation-context-optimizers context)
+        (compilation-context-positions context)
+        position)
+      context)))
 
 (define (compilation-context-push-local context variable)
   (compilation-context-append-locals context (list variable)))
@@ -1050,6 +1492,32 @@
 
   (unique (find expression)))
 
+; We keep global variables in a symbol table so that we can find them by names
+; on runtime.
+(define (find-global-symbols expression)
+  (let find ((locals '()) (expression expression))
+    (if (pair? expression)
+      (case (car expression)
+        (($$quote $$define-optimizer)
+          '())
+
+        (($$lambda)
+          (find
+            (append (parameter-names (cadr expression)) locals)
+            (caddr expression)))
+
+        (($$set!)
+          (let ((symbols (find locals (caddr expression))))
+            (if (memq (cadr expression) locals)
+              symbols
+              (cons (cadr expression) symbols))))
+
+        (else
+          (append
+            (find locals (car expression))
+            (find locals (cdr expression)))))
+      '())))
+
 (define (compile-arity argument-count variadic)
   (+
     (* 2 argument-count)
@@ -1086,7 +1554,7 @@
     (call-rib
       arity
       (compilation-context-resolve context procedure)
-      continuation)
+      (compile-position context continuation))
     (compile-expression
       context
       (car arguments)
@@ -1120,12 +1588,27 @@
           '$procedure
           (compile-unbind continuation))))))
 
+; Debug information is a nop instruction with a position before a
+; continuation. We cannot put it after a call in a tail position as it would
+; not be a tail call anymore.
+(define (compile-position context continuation)
+  (let ((position (compilation-context-position context)))
+    (if (and position (not (null? continuation)))
+      (code-rib nop-instruction position continuation)
+      continuation)))
+
 (define (compile-unbind continuation)
   (if (null? continuation)
     continuation
     (code-rib set-instruction 1 continuation)))
 
 (define (compile-expression context expression continuation)
+  (compile-raw-expression
+    (compilation-context-enter context expression)
+    expression
+    continuation))
+
+(define (compile-raw-expression context expression continuation)
   (cond
     ((symbol? expression)
       (code-rib
@@ -1164,13 +1647,15 @@
                 (compile-arity
                   (count-parameters parameters)
                   (symbol? (last-cdr parameters)))
-                (compile-sequence
-                  (compilation-context-append-locals
-                    context
-                    ; #f is for a frame.
-                    (reverse (cons #f (parameter-names parameters))))
-                  (cddr expression)
-                  '())
+                (compile-position
+                  context
+                  (compile-sequence
+                    (compilation-context-append-locals
+                      context
+                      ; #f is for a frame.
+                      (reverse (cons #f (parameter-names parameters))))
+                    (cddr expression)
+                    '()))
                 '())
               (call-rib (compile-arity 1 #f) '$$close continuation))))
 
@@ -1206,7 +1691,7 @@
     (else
       (constant-rib expression continuation))))
 
-(define (compile libraries macros optimizers expression)
+(define (compile libraries macros optimizers positions expression)
   (compile-expression
     (make-compilation-context
       '()
@@ -1217,12 +1702,15 @@
           (append
             (map car primitives)
             (find-symbols expression)
+            (find-global-symbols expression)
             (find-quoted-symbols libraries)
             (find-quoted-symbols macros)
             (find-quoted-symbols optimizers))))
       libraries
       macros
-      optimizers)
+      optimizers
+      positions
+      #f)
     expression
     '()))
 
@@ -1234,16 +1722,60 @@
   (constants marshal-context-constants marshal-context-set-constants!)
   (continuations marshal-context-continuations marshal-context-set-continuations!))
 
+; Nop instructions with positions are not shared continuations.
 (define (nop-code? codes)
   (and
     (rib? codes)
-    (eq? (rib-tag codes) nop-instruction)))
+    (eq? (rib-tag codes) nop-instruction)
+    (eq? (rib-car codes) 0)))
+
+; Exact integers out of a range of fixnums are marshalled as bignum ribs. We
+; halve them so that we never overflow on hosts without bignums.
+(define fixnum-half-limit (expt 2 61))
+
+(define (bignum-literal? value)
+  (and
+    (number? value)
+    (exact? value)
+    (integer? value)
+    (not
+      (<=
+        (- fixnum-half-limit)
+        (floor-quotient value 2)
+        (- fixnum-half-limit 1)))))
+
+; Bignum limbs are 32-bit and in a little-endian order.
+(define (integer->limbs x)
+  (if (zero? x)
+    '()
+    (cons
+      (remainder x 4294967296)
+      (integer->limbs (quotient x 4294967296)))))
+
+; Exact non-integer numbers are marshalled as rational number ribs.
+(define (rational-literal? value)
+  (and
+    (number? value)
+    (exact? value)
+    (not (integer? value))))
 
 (define (marshal-constant context value)
   (define (marshal value)
     (marshal-rib context value #t))
 
   (cond
+    ((bignum-literal? value)
+      (data-rib
+        bignum-type
+        (if (negative? value) -1 1)
+        (marshal (integer->limbs (abs value)))))
+
+    ((number? value)
+      (data-rib
+        rational-type
+        (marshal (numerator value))
+        (marshal (list (denominator value)))))
+
     ((null? value)
       (data-rib null-type 0 (cons-rib 0 0)))
 
@@ -1298,6 +1830,9 @@
     (marshal-rib context value data))
 
   (cond
+    ((or (bignum-literal? value) (rational-literal? value))
+      (marshal-constant context value))
+
     ((number? value)
       value)
 
@@ -1348,11 +1883,14 @@
 ;; Context
 
 (define-record-type encode-context
-  (make-encode-context dictionary counts null)
+  (make-encode-context dictionary counts null float bignum rational)
   encode-context?
   (dictionary encode-context-dictionary encode-context-set-dictionary!)
   (counts encode-context-counts encode-context-set-counts!)
-  (null encode-context-null))
+  (null encode-context-null)
+  (float encode-context-float encode-context-set-float!)
+  (bignum encode-context-bignum encode-context-set-bignum!)
+  (rational encode-context-rational encode-context-set-rational!))
 
 (define (encode-context-push! context value)
   (encode-context-set-dictionary!
@@ -1417,12 +1955,21 @@
 
 (define (count-ribs! context codes)
   (define (count-data! value)
-    (when (rib? value)
-      (unless (and (shared-value? value) (encode-context-find-count context value))
-        ((if (target-procedure? value) count-code! count-data!) (rib-car value))
-        (count-data! (rib-cdr value)))
-      (when (shared-value? value)
-        (increment-count! context value))))
+    (cond
+      ((rib? value)
+        (cond
+          ((eq? (rib-tag value) bignum-type)
+            (encode-context-set-bignum! context #t))
+          ((eq? (rib-tag value) rational-type)
+            (encode-context-set-rational! context #t)))
+        (unless (and (shared-value? value) (encode-context-find-count context value))
+          ((if (target-procedure? value) count-code! count-data!) (rib-car value))
+          (count-data! (rib-cdr value)))
+        (when (shared-value? value)
+          (increment-count! context value)))
+
+      ((and (number? value) (float-literal? value))
+        (encode-context-set-float! context #t))))
 
   (define (count-code! codes)
     (cond
@@ -1485,12 +2032,16 @@
         integer-base
         (if (zero? (quotient x integer-base)) 0 1)))))
 
+; Inexact non-integer numbers are encoded as floating-point numbers.
+(define (float-literal? x)
+  (and (inexact? x) (not (integer? x))))
+
 (define (encode-number x)
   (cond
     ((and (integer? x) (negative? x))
       (+ 1 (* 4 (abs x))))
 
-    ((integer? x)
+    ((not (float-literal? x))
       (* 2 x))
 
     (else
@@ -1552,6 +2103,27 @@
         (write-u8 (+ 7 (* 8 head)))
         (encode-integer-tail tail)))))
 
+;; Header
+
+(define magic-number '(137 83 84 75))
+(define format-version 1)
+(define float-flag 1)
+(define fingerprint-flag 2)
+(define bignum-flag 4)
+(define rational-flag 8)
+
+(define (encode-header context fingerprint)
+  (for-each write-u8 magic-number)
+  (write-u8 format-version)
+  (write-u8
+    (+
+      (if (encode-context-float context) float-flag 0)
+      (if fingerprint fingerprint-flag 0)
+      (if (encode-context-bignum context) bignum-flag 0)
+      (if (encode-context-rational context) rational-flag 0)))
+  (when fingerprint
+    (for-each write-u8 fingerprint)))
+
 ;; Primitives
 
 (define (build-primitive primitive continuation)
@@ -1573,14 +2145,15 @@
 
 ;; Main
 
-(define (encode codes)
-  (let ((context (make-encode-context '() '() (rib-car (rib-car codes)))))
+(define (encode codes fingerprint)
+  (let ((context (make-encode-context '() '() (rib-car (rib-car codes)) #f #f #f)))
     (count-ribs! context codes)
     (encode-context-set-counts!
       context
       (filter
         (lambda (pair) (> (cdr pair) 1))
         (encode-context-counts context)))
+    (encode-header context fingerprint)
     (encode-rib context codes)
 
     (let ((size (length (encode-context-dictionary context))))
@@ -1592,27 +2165,217 @@
       (unless (zero? (cdar counts))
         (error "invalid constant count" (map cdr counts))))))
 
-; Main
+; Diagnostics
+
+;; Data
+
+; We do not use string ports as some hosts do not support them.
+(define (datum->string datum)
+  (cond
+    ((null? datum)
+      "()")
+
+    ((pair? datum)
+      (let loop ((datum (cdr datum)) (strings (list (datum->string (car datum)) "(")))
+        (cond
+          ((pair? datum)
+            (loop (cdr datum) (cons (datum->string (car datum)) (cons " " strings))))
+
+          ((null? datum)
+            (apply string-append (reverse (cons ")" strings))))
+
+          (else
+            (apply string-append (reverse (cons ")" (cons (datum->string datum) (cons " . " strings)))))))))
+
+    ((symbol? datum)
+      (symbol->string datum))
+
+    ((number? datum)
+      (number->string datum))
+
+    ((string? datum)
+      (apply
+        string-append
+        (append
+          '("\"")
+          (map
+            (lambda (char)
+              (case char
+                ((#\")
+                  "\\\"")
 
-(define (main)
-  (define-values (expression1 libraries) (expand-libraries (read-source)))
-  (define-values (expression2 macros) (expand-macros expression1))
-  (define-values (expression3 optimizers) (optimize expression2))
+                ((#\\)
+                  "\\\\")
 
-  (encode
-    (marshal
-      (cons-rib
-        #f
-        (build-primitives
-          primitives
-          (compile libraries macros optimizers expression3))))))
+                ((#\newline)
+                  "\\n")
+
+                (else
+                  (string char))))
+            (string->list datum))
+          '("\""))))
+
+    ((char? datum)
+      (string #\# #\\ datum))
+
+    ((vector? datum)
+      (string-append "#" (datum->string (vector->list datum))))
+
+    ((eq? datum #t)
+      "#t")
+
+    ((eq? datum #f)
+      "#f")
+
+    (else
+      "#<unknown>")))
+
+;; Reporting
+
+; A host reports diagnostics through a primitive if it supports one. It takes
+; a warning message or `#f` for an error, an offending datum, a file name, a
+; line, and a column. Its index must match one in `stak_compiler`.
+(cond-expand
+  (stak
+    (define diagnostic-primitive ($$rib 900 '() procedure-type)))
+
+  (else
+    (define diagnostic-primitive #f)))
+
+(define (report-diagnostic message datum position)
+  (diagnostic-primitive
+    message
+    (datum->string datum)
+    (and position (car position))
+    (and position (cadr position))
+    (and position (caddr position))))
+
+; Otherwise, we write a position and an offending datum on a line before a
+; diagnostic message.
+(define (write-position position datum)
+  (parameterize ((current-output-port (current-error-port)))
+    (when (car position)
+      (write-string (car position))
+      (write-char #\:))
+    (write (cadr position))
+    (write-char #\:)
+    (write (caddr position))
+    (write-string ": ")
+    (write datum)
+    (newline)))
+
+(define (write-warning message datum position)
+  (when position
+    (write-position position datum))
+  (parameterize ((current-output-port (current-error-port)))
+    (write-string "warning: ")
+    (write-string message)
+    (write-char #\space)
+    (write datum)
+    (newline)))
+
+; An offending datum is the first irritant of an error.
+(define (report-error-position report error locate)
+  (let* ((datum
+           (and
+             (error-object? error)
+             (pair? (error-object-irritants error))
+             (car (error-object-irritants error))))
+         (position (and datum (locate datum))))
+    (when position
+      (if report
+        (report-diagnostic #f datum position)
+        (write-position position datum)))))
+
+; A warning is at the innermost one of expressions with a position.
+(define (report-warning report locate expressions message datum)
+  (let ((position
+          (let loop ((expressions expressions))
+            (and
+              (pair? expressions)
+              (or (locate (car expressions)) (loop (cdr expressions)))))))
+    (if report
+      (report-diagnostic message datum position)
+      (write-warning message datum position))))
+
+; Main
+
+; A fingerprint is a hexadecimal string of 8 bytes.
+(define (parse-fingerprint string)
+  (unless (= (string-length string) 16)
+    (error "invalid fingerprint" string))
+  (let loop ((index 0))
+    (if (= index 16)
+      '()
+      (cons
+        (or
+          (string->number (substring string index (+ index 2)) 16)
+          (error "invalid fingerprint" string))
+        (loop (+ index 2))))))
+
+(define (main fingerprint debug-info locations report warnings prelude)
+  ; We record positions only for debug information or locations in diagnostics.
+  (define positions (and (or debug-info locations) (make-position-table '())))
+
+  (define (locate expression)
+    (position-table-ref positions expression))
+
+  (with-exception-handler
+    (lambda (error)
+      (report-error-position report error locate)
+      (raise error))
+    (lambda ()
+      (define-values (expression1 libraries)
+        (expand-libraries
+          (read-source
+            prelude
+            (and
+              positions
+              (lambda (expression position)
+                (position-table-set! positions expression position))))
+          positions))
+      (define-values (expression2 macros) (expand-macros expression1 positions))
+
+      (when warnings
+        (check-expression
+          (lambda (expressions message datum)
+            (report-warning report locate expressions message datum))
+          expression2))
+
+      (define-values (expression3 optimizers) (optimize expression2 positions))
+
+      (encode
+        (marshal
+          (cons-rib
+            #f
+            (build-primitives
+              primitives
+              (compile libraries macros optimizers (and debug-info positions) expression3))))
+        fingerprint))))
 
 (let ((arguments (command-line)))
   (when (or
          (member "-h" arguments)
          (member "--help" arguments))
     (write-string "The Stak Scheme bytecode compiler.\n\n")
-    (write-string "Usage: stak-compile < SOURCE_FILE > BYTECODE_FILE\n")
-    (exit)))
-
-(main)
+    (write-string "Usage: stak-compile [--fingerprint FINGERPRINT] [--debug-info] [--locations] [--no-warnings] [--prelude PRELUDE_FILE] < SOURCE_FILE > BYTECODE_FILE\n")
+    (exit))
+
+  (main
+    (let ((arguments (member "--fingerprint" arguments)))
+      (and
+        arguments
+        (if (null? (cdr arguments))
+          (error "fingerprint expected")
+          (parse-fingerprint (cadr arguments)))))
+    (and (member "--debug-info" arguments) #t)
+    (and (member "--locations" arguments) #t)
+    ; Only hosts with a primitive for diagnostics pass this flag.
+    (and (member "--report-diagnostics" arguments) #t)
+    (not (member "--no-warnings" arguments))
+    (let ((arguments (member "--prelude" arguments)))
+      (and
+        arguments
+        (if (null? (cdr arguments))
+          (error "prelude expected")
+          (cadr arguments))))))
@@ -1,3 +1,4 @@
+use crate::Location;
 use core::{
     error,
     fmt::{self, Display, Formatter},
@@ -9,19 +10,31 @@ use stak_r7rs::SmallError;
 pub enum CompileError {
     /// A run error.
     Run(SmallError),
-    /// A user error.
-    User(String),
+    /// A user error with an optional location.
+    User(String, Option<Location>),
     /// A virtual machine error.
     Vm(stak_vm::Error),
 }
 
+impl CompileError {
+    /// Returns a location of an offending datum.
+    pub const fn location(&self) -> Option<&Location> {
+        if let Self::User(_, Some(location)) = self {
+            Some(location)
+        } else {
+            None
+        }
+    }
+}
+
 impl error::Error for CompileError {}
 
 impl Display for CompileError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
             Self::Run(error) => write!(formatter, "{error}"),
-            Self::User(error) => write!(formatter, "{error}"),
+            Self::User(error, None) => write!(formatter, "{error}"),
+            Self::User(error, Some(location)) => write!(formatter, "{location}: {error}"),
             Self::Vm(error) => write!(formatter, "{error}"),
         }
     }
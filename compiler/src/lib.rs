@@ -1,13 +1,22 @@
 //! Stak Scheme bytecode compiler.
 
 mod error;
+mod location;
+mod options;
+mod primitive_set;
+mod warning;
 
-pub use self::error::CompileError;
+use self::primitive_set::DiagnosticPrimitiveSet;
+pub use self::{
+    error::CompileError,
+    location::Location,
+    options::{CompileOptions, Prelude},
+    warning::Warning,
+};
 use core::env;
-use stak_configuration::DEFAULT_HEAP_SIZE;
 use stak_device::ReadWriteDevice;
 use stak_file::VoidFileSystem;
-use stak_process_context::VoidProcessContext;
+use stak_process_context::MemoryProcessContext;
 use stak_r7rs::SmallPrimitiveSet;
 use stak_time::VoidClock;
 use stak_vm::Vm;
@@ -15,6 +24,10 @@ use std::io::{Read, Write};
 
 const PRELUDE_SOURCE: &str = include_str!("prelude.scm");
 const COMPILER_BYTECODES: &[u8] = include_bytes!(env!("STAK_BYTECODE_FILE"));
+const DEBUG_INFO_OPTION: &str = "--debug-info";
+const LOCATIONS_OPTION: &str = "--locations";
+const REPORT_DIAGNOSTICS_OPTION: &str = "--report-diagnostics";
+const NO_WARNINGS_OPTION: &str = "--no-warnings";
 
 /// Compiles a program in R7RS Scheme into bytecodes.
 ///
@@ -27,7 +40,9 @@ const COMPILER_BYTECODES: &[u8] = include_bytes!(env!("STAK_BYTECODE_FILE"));
 /// stak_compiler::compile_r7rs(source.as_bytes(), &mut target).unwrap();
 /// ```
 pub fn compile_r7rs(source: impl Read, target: impl Write) -> Result<(), CompileError> {
-    compile_bare(PRELUDE_SOURCE.as_bytes().chain(source), target)
+    run(source, target, &CompileOptions::new(), false)?;
+
+    Ok(())
 }
 
 /// Compiles a program in Scheme into bytecodes with only built-ins.
@@ -41,37 +56,149 @@ pub fn compile_r7rs(source: impl Read, target: impl Write) -> Result<(), Compile
 /// stak_compiler::compile_bare(source.as_bytes(), &mut target).unwrap();
 /// ```
 pub fn compile_bare(source: impl Read, target: impl Write) -> Result<(), CompileError> {
-    // TODO Add a heap size option.
-    let mut heap = vec![Default::default(); DEFAULT_HEAP_SIZE];
+    run(
+        source,
+        target,
+        &CompileOptions::new().with_prelude(Prelude::Bare),
+        false,
+    )?;
+
+    Ok(())
+}
+
+/// Compiles a program in Scheme into bytecodes with options and returns
+/// warnings.
+///
+/// Warnings report references to undefined global variables and calls of
+/// procedures with wrong numbers of arguments after macro expansion.
+///
+/// # Examples
+///
+/// ```rust
+/// use stak_compiler::{CompileOptions, Prelude};
+///
+/// let source = "($$define x 42)";
+/// let mut target = vec![];
+///
+/// stak_compiler::compile(
+///     source.as_bytes(),
+///     &mut target,
+///     &CompileOptions::new()
+///         .with_heap_size(1 << 23)
+///         .with_prelude(Prelude::Bare),
+/// )
+/// .unwrap();
+/// ```
+pub fn compile(
+    source: impl Read,
+    target: impl Write,
+    options: &CompileOptions,
+) -> Result<Vec<Warning>, CompileError> {
+    run(source, target, options, true)
+}
+
+fn run(
+    source: impl Read,
+    target: impl Write,
+    options: &CompileOptions,
+    warnings: bool,
+) -> Result<Vec<Warning>, CompileError> {
+    let mut arguments = vec![REPORT_DIAGNOSTICS_OPTION];
+
+    if options.debug_info() {
+        arguments.push(DEBUG_INFO_OPTION);
+    }
+
+    if options.locations() {
+        arguments.push(LOCATIONS_OPTION);
+    }
+
+    if !warnings {
+        arguments.push(NO_WARNINGS_OPTION);
+    }
+
+    let mut heap = vec![Default::default(); options.heap_size()];
     let mut error_message = vec![];
-    let device = ReadWriteDevice::new(source, target, &mut error_message);
+    let marker = source_marker(options.name());
+    let device = ReadWriteDevice::new(
+        options
+            .prelude()
+            .source()
+            .as_bytes()
+            .chain(marker.as_bytes())
+            .chain(source),
+        target,
+        &mut error_message,
+    );
     let mut vm = Vm::new(
         &mut heap,
-        SmallPrimitiveSet::new(
+        DiagnosticPrimitiveSet::new(SmallPrimitiveSet::new(
             device,
             VoidFileSystem::new(),
-            VoidProcessContext::new(),
+            MemoryProcessContext::new(&arguments, &[]),
             VoidClock::new(),
-        ),
+        )),
     )?;
 
     vm.initialize(COMPILER_BYTECODES.iter().copied())?;
 
-    vm.run().map_err(|error| {
-        if error_message.is_empty() {
+    let result = vm.run();
+    let primitive_set = vm.primitive_set_mut();
+    let warnings = primitive_set.take_warnings();
+    let location = primitive_set.take_error_location();
+
+    if let Err(error) = result {
+        return Err(if error_message.is_empty() {
             CompileError::Run(error)
         } else {
-            CompileError::User(String::from_utf8_lossy(&error_message).into_owned())
+            CompileError::User(
+                String::from_utf8_lossy(&error_message).into_owned(),
+                location,
+            )
+        });
+    }
+
+    Ok(warnings)
+}
+
+/// Returns a marker that resets source positions after a prelude.
+///
+/// A name is written as a string literal in Scheme.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(stak_compiler::source_marker(None), "($$source)");
+/// assert_eq!(
+///     stak_compiler::source_marker(Some("foo\\bar.scm")),
+///     r#"($$source "foo\\bar.scm")"#
+/// );
+/// ```
+pub fn source_marker(name: Option<&str>) -> String {
+    let Some(name) = name else {
+        return "($$source)".into();
+    };
+
+    let mut marker = String::from("($$source \"");
+
+    for character in name.chars() {
+        if matches!(character, '"' | '\\') {
+            marker.push('\\');
         }
-    })?;
 
-    Ok(())
+        marker.push(character);
+    }
+
+    marker.push_str("\")");
+
+    marker
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
+    use stak_configuration::DEFAULT_HEAP_SIZE;
 
     mod bare {
         use super::*;
@@ -87,6 +214,64 @@ mod tests {
         }
     }
 
+    mod options {
+        use super::*;
+
+        #[test]
+        fn compile_with_custom_prelude() {
+            assert!(compile(
+                b"($$define y x)".as_slice(),
+                &mut vec![],
+                &CompileOptions::new().with_prelude(Prelude::Custom("($$define x 42)".into())),
+            )
+            .unwrap()
+            .is_empty());
+        }
+
+        #[test]
+        fn compile_without_prelude() {
+            assert_eq!(
+                compile(
+                    b"($$define y x)".as_slice(),
+                    &mut vec![],
+                    &CompileOptions::new().with_prelude(Prelude::Bare),
+                )
+                .unwrap(),
+                [Warning::new("undefined global variable x".into(), None)]
+            );
+        }
+
+        #[test]
+        fn compile_with_locations() {
+            assert_eq!(
+                compile(
+                    b"($$define y x)".as_slice(),
+                    &mut vec![],
+                    &CompileOptions::new()
+                        .with_prelude(Prelude::Bare)
+                        .with_locations(true),
+                )
+                .unwrap(),
+                [Warning::new(
+                    "undefined global variable x".into(),
+                    Some(Location::new(None, 1, 1, "x".into()))
+                )]
+            );
+        }
+
+        #[test]
+        fn compile_with_small_heap() {
+            assert!(compile(
+                b"".as_slice(),
+                &mut vec![],
+                &CompileOptions::new()
+                    .with_heap_size(1 << 10)
+                    .with_prelude(Prelude::Bare),
+            )
+            .is_err());
+        }
+    }
+
     mod r7rs {
         use super::*;
 
@@ -102,7 +287,7 @@ mod tests {
 
         #[test]
         fn compile_invalid_macro_call() {
-            let Err(CompileError::User(message)) = compile_r7rs(
+            let Err(CompileError::User(message, location)) = compile(
                 indoc!(
                     r#"
                     (import (scheme base))
@@ -117,16 +302,100 @@ mod tests {
                 )
                 .as_bytes(),
                 &mut vec![],
+                &CompileOptions::new().with_locations(true),
             ) else {
                 panic!()
             };
 
             assert!(message.contains("invalid syntax"));
+            assert_eq!(location, Some(Location::new(None, 8, 1, "(foo 42)".into())));
         }
 
         #[test]
         fn compile_write_library() {
             compile_r7rs(b"(import (scheme write))".as_slice(), &mut vec![]).unwrap();
         }
+
+        #[test]
+        fn compile_with_warnings() {
+            assert_eq!(
+                compile(
+                    indoc!(
+                        r#"
+                        (import (scheme base))
+
+                        (define (foo x) x)
+
+                        (foo)
+                        (bar)
+                        "#
+                    )
+                    .as_bytes(),
+                    &mut vec![],
+                    &CompileOptions::new().with_locations(true),
+                )
+                .unwrap(),
+                [
+                    Warning::new(
+                        "wrong number of arguments (foo)".into(),
+                        Some(Location::new(None, 5, 1, "(foo)".into()))
+                    ),
+                    Warning::new(
+                        "undefined global variable bar".into(),
+                        Some(Location::new(None, 6, 1, "bar".into()))
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn compile_with_debug_info() {
+            let mut bytecodes = vec![];
+
+            compile(
+                indoc!(
+                    r#"
+                    (import (scheme base))
+
+                    (define foo #f)
+
+                    (define (bar)
+                      (foo)
+                      #f)
+
+                    (bar)
+                    #f
+                    "#
+                )
+                .as_bytes(),
+                &mut bytecodes,
+                &CompileOptions::new()
+                    .with_name("main.scm")
+                    .with_debug_info(true),
+            )
+            .unwrap();
+
+            let mut heap = vec![Default::default(); DEFAULT_HEAP_SIZE];
+            let mut vm = Vm::new(
+                &mut heap,
+                SmallPrimitiveSet::new(
+                    ReadWriteDevice::new(b"".as_slice(), vec![], vec![]),
+                    VoidFileSystem::new(),
+                    MemoryProcessContext::new(&[], &[]),
+                    VoidClock::new(),
+                ),
+            )
+            .unwrap();
+
+            vm.initialize(bytecodes).unwrap();
+            vm.run().unwrap_err();
+
+            assert_eq!(
+                vm.backtrace()
+                    .map(|frame| frame.to_string())
+                    .collect::<Vec<_>>(),
+                ["foo (main.scm:6:3)", "bar (main.scm:9:1)"]
+            );
+        }
     }
 }
@@ -0,0 +1,52 @@
+use core::fmt::{self, Display, Formatter};
+
+/// A source location of an offending datum in a compile error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    file: Option<String>,
+    line: usize,
+    column: usize,
+    datum: String,
+}
+
+impl Location {
+    /// Creates a location.
+    pub const fn new(file: Option<String>, line: usize, column: usize, datum: String) -> Self {
+        Self {
+            file,
+            line,
+            column,
+            datum,
+        }
+    }
+
+    /// Returns a file name.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns a line number starting from 1.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns a column number in characters starting from 1.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns an offending datum written in Scheme.
+    pub fn datum(&self) -> &str {
+        &self.datum
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(formatter, "{file}:")?;
+        }
+
+        write!(formatter, "{}:{}", self.line, self.column)
+    }
+}
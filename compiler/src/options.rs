@@ -0,0 +1,115 @@
+use crate::PRELUDE_SOURCE;
+use stak_configuration::DEFAULT_HEAP_SIZE;
+
+/// A prelude prepended to source codes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum Prelude {
+    /// The R7RS standard libraries.
+    #[default]
+    R7rs,
+    /// No prelude with only built-ins.
+    Bare,
+    /// A custom prelude.
+    Custom(String),
+}
+
+impl Prelude {
+    /// Returns source codes.
+    pub fn source(&self) -> &str {
+        match self {
+            Self::R7rs => PRELUDE_SOURCE,
+            Self::Bare => "",
+            Self::Custom(source) => source,
+        }
+    }
+}
+
+/// Compile options.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompileOptions {
+    heap_size: usize,
+    prelude: Prelude,
+    name: Option<String>,
+    debug_info: bool,
+    locations: bool,
+}
+
+impl CompileOptions {
+    /// Creates compile options.
+    pub const fn new() -> Self {
+        Self {
+            heap_size: DEFAULT_HEAP_SIZE,
+            prelude: Prelude::R7rs,
+            name: None,
+            debug_info: false,
+            locations: false,
+        }
+    }
+
+    /// Sets a heap size of a compiler in cells.
+    pub fn with_heap_size(self, heap_size: usize) -> Self {
+        Self { heap_size, ..self }
+    }
+
+    /// Sets a prelude.
+    pub fn with_prelude(self, prelude: Prelude) -> Self {
+        Self { prelude, ..self }
+    }
+
+    /// Sets a source name.
+    ///
+    /// Locations in errors and debug information use it as a file name.
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Enables or disables debug information.
+    ///
+    /// Bytecodes with debug information record source positions of procedures
+    /// and call sites.
+    pub fn with_debug_info(self, debug_info: bool) -> Self {
+        Self { debug_info, ..self }
+    }
+
+    /// Enables or disables locations of offending data in errors and warnings.
+    ///
+    /// It is costly to record source positions of every datum. Debug
+    /// information also enables locations.
+    pub fn with_locations(self, locations: bool) -> Self {
+        Self { locations, ..self }
+    }
+
+    /// Returns a heap size of a compiler in cells.
+    pub const fn heap_size(&self) -> usize {
+        self.heap_size
+    }
+
+    /// Returns a prelude.
+    pub const fn prelude(&self) -> &Prelude {
+        &self.prelude
+    }
+
+    /// Returns a source name.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns `true` if debug information is enabled.
+    pub const fn debug_info(&self) -> bool {
+        self.debug_info
+    }
+
+    /// Returns `true` if locations are enabled.
+    pub const fn locations(&self) -> bool {
+        self.locations
+    }
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Location, Warning};
+use core::mem::take;
+use stak_r7rs::SmallError;
+use stak_vm::{Error, Memory, PrimitiveSet, Type, Value};
+
+// A primitive index out of ranges of R7RS primitives and dynamic primitives of
+// engines. It must match one in `compile.scm`.
+const DIAGNOSTIC_PRIMITIVE: usize = 900;
+
+// A primitive set that receives diagnostics from a compiler.
+pub struct DiagnosticPrimitiveSet<T: PrimitiveSet<Error = SmallError>> {
+    primitive_set: T,
+    warnings: Vec<Warning>,
+    error_location: Option<Location>,
+}
+
+impl<T: PrimitiveSet<Error = SmallError>> DiagnosticPrimitiveSet<T> {
+    pub const fn new(primitive_set: T) -> Self {
+        Self {
+            primitive_set,
+            warnings: vec![],
+            error_location: None,
+        }
+    }
+
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        take(&mut self.warnings)
+    }
+
+    pub const fn take_error_location(&mut self) -> Option<Location> {
+        self.error_location.take()
+    }
+
+    // A diagnostic is a warning message or `#f` for an error, an offending datum,
+    // a file name, a line, and a column.
+    fn report(&mut self, memory: &mut Memory) -> Result<(), SmallError> {
+        let [message, datum, file, line, column] = memory.pop_many();
+        let datum = read_string(memory, datum)?;
+        let location = if let (Some(line), Some(column)) = (line.to_number(), column.to_number()) {
+            Some(Location::new(
+                if file == memory.boolean(false).into() {
+                    None
+                } else {
+                    Some(read_string(memory, file)?)
+                },
+                line.to_i64() as _,
+                column.to_i64() as _,
+                datum.clone(),
+            ))
+        } else {
+            None
+        };
+
+        if message == memory.boolean(false).into() {
+            self.error_location = location;
+        } else {
+            self.warnings.push(Warning::new(
+                format!("{} {datum}", read_string(memory, message)?),
+                location,
+            ));
+        }
+
+        memory.push(memory.boolean(false).into())?;
+
+        Ok(())
+    }
+}
+
+impl<T: PrimitiveSet<Error = SmallError>> PrimitiveSet for DiagnosticPrimitiveSet<T> {
+    type Error = SmallError;
+
+    fn operate(&mut self, memory: &mut Memory, primitive: usize) -> Result<(), Self::Error> {
+        if primitive == DIAGNOSTIC_PRIMITIVE {
+            self.report(memory)
+        } else {
+            self.primitive_set.operate(memory, primitive)
+        }
+    }
+
+    fn collect_garbages(&mut self, memory: &Memory) -> Result<(), Self::Error> {
+        self.primitive_set.collect_garbages(memory)
+    }
+}
+
+fn read_string(memory: &Memory, value: Value) -> Result<String, Error> {
+    let cons = value.to_cons().ok_or(Error::ConsExpected)?;
+
+    if memory.cdr(cons).tag() != Type::String as _ {
+        return Err(Error::ConsExpected);
+    }
+
+    let mut string = String::new();
+    let mut cons = memory.cdr(cons).to_cons().ok_or(Error::ConsExpected)?;
+
+    while cons != memory.null() {
+        string.extend(char::from_u32(
+            memory
+                .car(cons)
+                .to_number()
+                .ok_or(Error::NumberExpected)?
+                .to_i64() as _,
+        ));
+        cons = memory.cdr(cons).to_cons().ok_or(Error::ConsExpected)?;
+    }
+
+    Ok(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stak_device::VoidDevice;
+    use stak_file::VoidFileSystem;
+    use stak_process_context::VoidProcessContext;
+    use stak_r7rs::SmallPrimitiveSet;
+    use stak_time::VoidClock;
+    use stak_vm::Number;
+
+    const HEAP_SIZE: usize = 1 << 10;
+
+    fn primitive_set() -> DiagnosticPrimitiveSet<
+        SmallPrimitiveSet<VoidDevice, VoidFileSystem, VoidProcessContext, VoidClock>,
+    > {
+        DiagnosticPrimitiveSet::new(SmallPrimitiveSet::new(
+            VoidDevice::new(),
+            VoidFileSystem::new(),
+            VoidProcessContext::new(),
+            VoidClock::new(),
+        ))
+    }
+
+    fn push_string(memory: &mut Memory, string: &str) {
+        let mut list = memory.null();
+
+        for character in string.chars().rev() {
+            list = memory
+                .cons(Number::from_i64(character as _).into(), list)
+                .unwrap();
+        }
+
+        let string = memory
+            .allocate(
+                Number::from_i64(string.chars().count() as _).into(),
+                list.set_tag(Type::String as _).into(),
+            )
+            .unwrap();
+
+        memory.push(string.into()).unwrap();
+    }
+
+    fn push_false(memory: &mut Memory) {
+        memory.push(memory.boolean(false).into()).unwrap();
+    }
+
+    fn push_number(memory: &mut Memory, number: i64) {
+        memory.push(Number::from_i64(number).into()).unwrap();
+    }
+
+    #[test]
+    fn report_warning() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+        let mut primitive_set = primitive_set();
+
+        push_string(&mut memory, "undefined global variable");
+        push_string(&mut memory, "foo");
+        push_false(&mut memory);
+        push_false(&mut memory);
+        push_false(&mut memory);
+
+        primitive_set
+            .operate(&mut memory, DIAGNOSTIC_PRIMITIVE)
+            .unwrap();
+
+        assert_eq!(
+            primitive_set.take_warnings(),
+            [Warning::new("undefined global variable foo".into(), None)]
+        );
+        assert_eq!(primitive_set.take_error_location(), None);
+    }
+
+    #[test]
+    fn report_warning_with_location() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+        let mut primitive_set = primitive_set();
+
+        push_string(&mut memory, "wrong number of arguments");
+        push_string(&mut memory, "(foo)");
+        push_string(&mut memory, "foo.scm");
+        push_number(&mut memory, 8);
+        push_number(&mut memory, 2);
+
+        primitive_set
+            .operate(&mut memory, DIAGNOSTIC_PRIMITIVE)
+            .unwrap();
+
+        assert_eq!(
+            primitive_set.take_warnings(),
+            [Warning::new(
+                "wrong number of arguments (foo)".into(),
+                Some(Location::new(Some("foo.scm".into()), 8, 2, "(foo)".into()))
+            )]
+        );
+    }
+
+    #[test]
+    fn report_error_location() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+        let mut primitive_set = primitive_set();
+
+        push_false(&mut memory);
+        push_string(&mut memory, "(foo 42)");
+        push_false(&mut memory);
+        push_number(&mut memory, 8);
+        push_number(&mut memory, 1);
+
+        primitive_set
+            .operate(&mut memory, DIAGNOSTIC_PRIMITIVE)
+            .unwrap();
+
+        assert!(primitive_set.take_warnings().is_empty());
+        assert_eq!(
+            primitive_set.take_error_location(),
+            Some(Location::new(None, 8, 1, "(foo 42)".into()))
+        );
+    }
+
+    #[test]
+    fn match_diagnostic_primitive_in_compiler() {
+        assert!(include_str!("compile.scm").contains(&format!(
+            "(define diagnostic-primitive ($$rib {DIAGNOSTIC_PRIMITIVE} '() procedure-type))"
+        )));
+    }
+}
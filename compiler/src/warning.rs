@@ -0,0 +1,36 @@
+use crate::Location;
+use core::fmt::{self, Display, Formatter};
+
+/// A compile warning.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Warning {
+    message: String,
+    location: Option<Location>,
+}
+
+impl Warning {
+    /// Creates a warning.
+    pub const fn new(message: String, location: Option<Location>) -> Self {
+        Self { message, location }
+    }
+
+    /// Returns a message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns a location of an offending datum.
+    pub const fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(formatter, "{location}: ")?;
+        }
+
+        write!(formatter, "warning: {}", self.message)
+    }
+}
@@ -0,0 +1,132 @@
+//! Utilities for derived implementations of [`SchemeValue`].
+
+use crate::{DynamicError, SchemeValue};
+use alloc::string::String;
+use stak_vm::{Memory, Number, Type, Value};
+
+/// Returns `true` if a value is a symbol of a name.
+pub fn is_symbol(memory: &Memory, value: Value, name: &str) -> bool {
+    value.to_cons().is_some_and(|symbol| {
+        memory.cdr(symbol).tag() == Type::Symbol as _ && is_string(memory, memory.cdr(symbol), name)
+    })
+}
+
+fn is_string(memory: &Memory, value: Value, name: &str) -> bool {
+    let Some(string) = value.to_cons() else {
+        return false;
+    };
+
+    if memory.cdr(string).tag() != Type::String as _
+        || memory.car(string) != Number::from_i64(name.chars().count() as _).into()
+    {
+        return false;
+    }
+
+    let mut list = memory.cdr(string);
+
+    for character in name.chars() {
+        match list.to_cons() {
+            Some(cons)
+                if cons != memory.null()
+                    && memory.car(cons) == Number::from_i64(character as _).into() =>
+            {
+                list = memory.cdr(cons);
+            }
+            _ => return false,
+        }
+    }
+
+    list.to_cons() == Some(memory.null())
+}
+
+/// Returns a symbol of a name.
+///
+/// We intern a symbol into a symbol table of Scheme kept in a `car` of `#t` so
+/// that it is `eq?` to a symbol of the same name in a program.
+pub fn symbol(memory: &mut Memory, name: &str) -> Result<Value, DynamicError> {
+    let mut list = memory.car(memory.boolean(true));
+
+    while let Some(cons) = list.to_cons() {
+        if cons == memory.null() {
+            break;
+        } else if is_symbol(memory, memory.car(cons), name) {
+            return Ok(memory.car(cons));
+        }
+
+        list = memory.cdr(cons);
+    }
+
+    let string = String::from(name).into_scheme(memory)?;
+    let symbol = memory.allocate(
+        memory.boolean(false).into(),
+        string.set_tag(Type::Symbol as _),
+    )?;
+    let symbols = memory
+        .car(memory.boolean(true))
+        .to_cons()
+        .unwrap_or(memory.null());
+    let symbols = memory.cons(symbol.into(), symbols)?;
+    memory.set_car(memory.boolean(true), symbols.into());
+
+    Ok(memory.car(symbols))
+}
+
+/// Returns a value of a field in an association list.
+pub fn field(memory: &Memory, list: Value, name: &str) -> Option<Value> {
+    let mut list = list.to_cons()?;
+
+    while list != memory.null() {
+        let entry = memory.car(list).to_cons()?;
+
+        if is_symbol(memory, memory.car(entry), name) {
+            return Some(memory.cdr(entry));
+        }
+
+        list = memory.cdr(list).to_cons()?;
+    }
+
+    None
+}
+
+/// Returns an element at an index in a list.
+pub fn element(memory: &Memory, list: Value, index: usize) -> Option<Value> {
+    let mut list = list.to_cons()?;
+
+    for _ in 0..index {
+        if list == memory.null() {
+            return None;
+        }
+
+        list = memory.cdr(list).to_cons()?;
+    }
+
+    (list != memory.null()).then(|| memory.car(list))
+}
+
+/// Prepends a field to an association list at the top of a stack.
+pub fn push_field<T: SchemeValue>(
+    memory: &mut Memory,
+    name: &str,
+    value: T,
+) -> Result<(), DynamicError> {
+    let value = value.into_scheme(memory)?;
+    memory.push(value)?;
+    let key = symbol(memory, name)?;
+    let value = memory.pop();
+    let entry = memory.allocate(key, value.set_tag(Type::Pair as _))?;
+    let list = memory.pop();
+    let list = memory.cons(entry.into(), list.assume_cons())?;
+    memory.push(list.into())?;
+
+    Ok(())
+}
+
+/// Prepends an element to a list at the top of a stack.
+pub fn push_element<T: SchemeValue>(memory: &mut Memory, value: T) -> Result<(), DynamicError> {
+    let value = value.into_scheme(memory)?;
+    let list = memory.pop();
+    let list = memory.cons(value, list.assume_cons())?;
+    memory.push(list.into())?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use alloc::string::String;
 use any_fn::AnyFnError;
 use core::{
     error::Error,
@@ -9,6 +10,8 @@ use core::{
 pub enum DynamicError {
     /// An `AnyFn` error.
     AnyFn(AnyFnError),
+    /// A function error.
+    Function(String),
     /// A foreign value expected.
     ForeignValueExpected,
     /// A value index error.
@@ -35,6 +38,7 @@ impl Display for DynamicError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
             Self::AnyFn(error) => write!(formatter, "{error}"),
+            Self::Function(message) => write!(formatter, "{message}"),
             Self::ForeignValueExpected => write!(formatter, "foreign value expected"),
             Self::ValueIndex => write!(formatter, "invalid value index"),
             Self::Vm(error) => write!(formatter, "{error}"),
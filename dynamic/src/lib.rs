@@ -4,6 +4,7 @@
 
 extern crate alloc;
 
+mod derive;
 mod error;
 mod primitive_set;
 mod scheme_value;
@@ -11,3 +12,9 @@ mod scheme_value;
 pub use error::*;
 pub use primitive_set::*;
 pub use scheme_value::*;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::derive::*;
+    pub use stak_vm;
+}
@@ -1,23 +1,58 @@
 use crate::{error::DynamicError, scheme_value::SchemeValue};
-use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec};
 use any_fn::AnyFn;
 use bitvec::bitvec;
-use core::any::TypeId;
+use core::{
+    any::{Any, TypeId},
+    fmt::Display,
+};
 use stak_vm::{Cons, Error, Memory, Number, PrimitiveSet, Type, Value};
 
 const MAXIMUM_ARGUMENT_COUNT: usize = 16;
 
 type ArgumentVec<T> = heapless::Vec<T, MAXIMUM_ARGUMENT_COUNT>;
 type SchemeType = (
-    TypeId,
     Box<dyn Fn(&Memory, Value) -> Option<any_fn::Value>>,
     Box<dyn Fn(&mut Memory, any_fn::Value) -> Result<Value, DynamicError>>,
 );
+type Finalizer<'a> = (TypeId, Box<dyn FnMut(any_fn::Value) + 'a>);
+
+// Calls a macro with scalar types registered by default.
+macro_rules! with_scalar_types {
+    ($macro:ident!($($argument:tt)*)) => {
+        $macro!(
+            $($argument)* bool, i8, u8, i16, u16, i32, u32, i64, u64, f32, f64, isize, usize, char,
+            String
+        )
+    };
+}
+
+// Registers scalar types together with tuples of all their combinations.
+macro_rules! register_scalar_types {
+    ($set:ident, $($type:ty),*) => {
+        $($set.register_scalar_type::<$type>();)*
+        register_scalar_types!(@tuple $set, [$($type),*], [$($type),*]);
+    };
+    (@tuple $set:ident, [$($first:ty),*], $types:tt) => {
+        $(register_scalar_types!(@pair $set, $first, $types);)*
+    };
+    (@pair $set:ident, $first:ty, [$($second:ty),*]) => {
+        $($set.register_type::<($first, $second)>();)*
+    };
+}
+
+// Registers result types of scalar types with an error type.
+macro_rules! register_result_types {
+    ($set:ident, $error:ty, $($type:ty),*) => {
+        $($set.register_type::<Result<$type, $error>>();)*
+    };
+}
 
 /// A dynamic primitive set equipped with native functions in Rust.
 pub struct DynamicPrimitiveSet<'a, 'b> {
     functions: &'a mut [AnyFn<'b>],
-    types: Vec<SchemeType>,
+    types: BTreeMap<TypeId, SchemeType>,
+    finalizers: Vec<Finalizer<'b>>,
     values: Vec<Option<any_fn::Value>>,
 }
 
@@ -26,24 +61,15 @@ impl<'a, 'b> DynamicPrimitiveSet<'a, 'b> {
     pub fn new(functions: &'a mut [AnyFn<'b>]) -> Self {
         let mut set = Self {
             functions,
-            types: vec![],
+            types: BTreeMap::new(),
+            finalizers: vec![],
             values: vec![],
         };
 
-        set.register_type::<bool>();
-        set.register_type::<i8>();
-        set.register_type::<u8>();
-        set.register_type::<i16>();
-        set.register_type::<u16>();
-        set.register_type::<i32>();
-        set.register_type::<u32>();
-        set.register_type::<i64>();
-        set.register_type::<u64>();
-        set.register_type::<f32>();
-        set.register_type::<f64>();
-        set.register_type::<isize>();
-        set.register_type::<usize>();
-        set.register_type::<String>();
+        with_scalar_types!(register_scalar_types!(set,));
+        set.register_error_type::<String>();
+        set.register_error_type::<&'static str>();
+        set.register_error_type::<Box<dyn core::error::Error>>();
 
         set
     }
@@ -55,40 +81,128 @@ impl<'a, 'b> DynamicPrimitiveSet<'a, 'b> {
     /// of information (e.g. floating-point numbers in Scheme marshalled
     /// into integers in Rust.)
     pub fn register_type<T: SchemeValue + 'static>(&mut self) {
-        self.types.push((
+        self.types.insert(
+            TypeId::of::<T>(),
+            (
+                Box::new(|memory, value| T::from_scheme(memory, value).map(any_fn::value)),
+                Box::new(|memory, value| T::into_scheme(value.downcast()?, memory)),
+            ),
+        );
+    }
+
+    /// Registers an error type of fallible functions.
+    ///
+    /// Results of `Result<T, E>` for scalar types `T` and the error type `E`
+    /// are converted into error objects raised in Scheme on failures.
+    /// `String`, `&'static str`, and `Box<dyn Error>` are registered by
+    /// default. Results of the other types are kept as foreign values.
+    pub fn register_error_type<E: Display + 'static>(&mut self) {
+        let set = self;
+
+        with_scalar_types!(register_result_types!(set, E,));
+    }
+
+    /// Registers a finalizer for foreign values of a type.
+    ///
+    /// A finalizer is called with a foreign value when it gets unreachable
+    /// from Scheme after garbage collection or when the primitive set is
+    /// dropped.
+    pub fn register_finalizer<T: Any>(&mut self, mut finalize: impl FnMut(T) + 'b) {
+        self.finalizers.push((
             TypeId::of::<T>(),
-            Box::new(|memory, value| T::from_scheme(memory, value).map(any_fn::value)),
-            Box::new(|memory, value| T::into_scheme(value.downcast()?, memory)),
+            Box::new(move |value| {
+                if let Ok(value) = value.downcast() {
+                    finalize(value);
+                }
+            }),
         ));
     }
 
-    fn collect_garbages(&mut self, memory: &Memory) {
+    // Registers a type together with collection types of it.
+    fn register_scalar_type<T: SchemeValue + 'static>(&mut self) {
+        self.register_type::<T>();
+        self.register_type::<Vec<T>>();
+        self.register_type::<Option<T>>();
+        self.register_type::<BTreeMap<String, T>>();
+    }
+
+    // Releases foreign values unreachable from Scheme.
+    fn release(&mut self, memory: &Memory) {
         let mut marks = bitvec![0; self.values.len()];
 
         for index in 0..(memory.allocation_index() / 2) {
             let cons = Cons::new((memory.allocation_start() + 2 * index) as _);
 
-            if memory.cdr(cons).tag() != Type::Foreign as _ {
-                continue;
-            }
-
-            let index = memory.car(cons).assume_number().to_i64() as _;
-
-            // Be conservative as foreign type tags can be used for something else.
-            if index >= self.values.len() {
-                continue;
+            if let Some(index) = Self::foreign_index(memory, cons.into()) {
+                if index < self.values.len() {
+                    marks.set(index, true);
+                }
             }
-
-            marks.set(index, true);
         }
 
         for (index, mark) in marks.into_iter().enumerate() {
             if !mark {
-                self.values[index] = None;
+                if let Some(value) = self.values[index].take() {
+                    self.finalize(value);
+                }
             }
         }
     }
 
+    fn finalize(&mut self, value: any_fn::Value) {
+        let Ok(type_id) = value.type_id() else {
+            return;
+        };
+
+        if let Some((_, finalize)) = self.finalizers.iter_mut().find(|(id, _)| *id == type_id) {
+            finalize(value);
+        }
+    }
+
+    // A foreign value is a pair of its index and a `#f` tagged as a foreign type.
+    // Using the unique `#f` object distinguishes foreign values from other
+    // objects with the same tag, such as instructions.
+    fn foreign_index(memory: &Memory, value: Value) -> Option<usize> {
+        let cons = value.to_cons()?;
+
+        let tag = memory.cdr(cons);
+
+        if tag.tag() != Type::Foreign as _ || tag != Self::foreign_tag(memory).into() {
+            return None;
+        }
+
+        Some(memory.car(cons).to_number()?.to_i64() as _)
+    }
+
+    const fn foreign_tag(memory: &Memory) -> Cons {
+        memory.boolean(false).set_tag(Type::Foreign as _)
+    }
+
+    // Raises an error with a handler procedure in an environment of a primitive
+    // procedure.
+    fn raise(memory: &mut Memory, message: String) -> Result<(), DynamicError> {
+        if Self::handler(memory).is_none() {
+            return Err(DynamicError::Function(message));
+        }
+
+        let message = message.into_scheme(memory)?;
+        memory.push(message)?;
+        let handler = Self::handler(memory).ok_or(Error::ProcedureExpected)?;
+        memory.call(handler.into(), 1)?;
+
+        Ok(())
+    }
+
+    fn handler(memory: &Memory) -> Option<Cons> {
+        let environment = memory.cdr(memory.register());
+        let handler = environment.to_cons()?;
+
+        (environment.tag() == Type::Procedure as _
+            && handler != memory.null()
+            && memory.cdr(handler).tag() == Type::Procedure as _)
+            .then_some(handler.set_tag(Type::Pair as _))
+    }
+
     // TODO Optimize this with `BitSlice::first_zero()`.
     fn find_free(&self) -> Option<usize> {
         self.values.iter().position(Option::is_none)
@@ -98,7 +212,7 @@ impl<'a, 'b> DynamicPrimitiveSet<'a, 'b> {
         if let Some(index) = self.find_free() {
             index
         } else if let Some(index) = {
-            self.collect_garbages(memory);
+            self.release(memory);
             self.find_free()
         } {
             index
@@ -114,24 +228,22 @@ impl<'a, 'b> DynamicPrimitiveSet<'a, 'b> {
         value: Value,
         type_id: TypeId,
     ) -> Option<any_fn::Value> {
-        for (id, from, _) in &self.types {
-            if type_id == *id {
-                return from(memory, value);
-            }
-        }
+        let (from, _) = self.types.get(&type_id)?;
 
-        None
+        from(memory, value)
     }
 
-    fn convert_into_scheme(
+    /// Converts a Rust value into a Scheme value.
+    ///
+    /// Values of registered types are marshalled into Scheme values while the
+    /// others are kept as foreign values.
+    pub fn convert_into_scheme(
         &mut self,
         memory: &mut Memory,
         value: any_fn::Value,
     ) -> Result<Value, DynamicError> {
-        for (id, _, into) in &self.types {
-            if value.type_id()? == *id {
-                return into(memory, value);
-            }
+        if let Some((_, into)) = self.types.get(&value.type_id()?) {
+            return into(memory, value);
         }
 
         let index = self.allocate(memory);
@@ -141,12 +253,22 @@ impl<'a, 'b> DynamicPrimitiveSet<'a, 'b> {
         Ok(memory
             .allocate(
                 Number::from_i64(index as _).into(),
-                memory.null().set_tag(Type::Foreign as _).into(),
+                Self::foreign_tag(memory).into(),
             )?
             .into())
     }
 }
 
+impl Drop for DynamicPrimitiveSet<'_, '_> {
+    fn drop(&mut self) {
+        for index in 0..self.values.len() {
+            if let Some(value) = self.values[index].take() {
+                self.finalize(value);
+            }
+        }
+    }
+}
+
 impl PrimitiveSet for DynamicPrimitiveSet<'_, '_> {
     type Error = DynamicError;
 
@@ -174,10 +296,10 @@ impl PrimitiveSet for DynamicPrimitiveSet<'_, '_> {
         let mut copied_arguments = ArgumentVec::new();
 
         for &value in &arguments {
-            let value = if value.is_cons() && memory.cdr_value(value).tag() == Type::Foreign as _ {
+            let value = if let Some(index) = Self::foreign_index(memory, value) {
                 Some(
                     self.values
-                        .get(memory.car_value(value).assume_number().to_i64() as usize)
+                        .get(index)
                         .ok_or(DynamicError::ValueIndex)?
                         .as_ref()
                         .ok_or(DynamicError::ValueIndex)?,
@@ -208,8 +330,21 @@ impl PrimitiveSet for DynamicPrimitiveSet<'_, '_> {
                     .as_slice(),
             )?;
 
-        let value = self.convert_into_scheme(memory, value)?;
-        memory.push(value)?;
+        match self.convert_into_scheme(memory, value) {
+            Ok(value) => memory.push(value)?,
+            Err(DynamicError::Function(message)) => Self::raise(memory, message)?,
+            Err(error) => return Err(error),
+        }
+
+        Ok(())
+    }
+
+    /// Releases foreign values unreachable from Scheme.
+    ///
+    /// Virtual machines call this method right after garbage collection, when
+    /// only live objects are on heap.
+    fn collect_garbages(&mut self, memory: &Memory) -> Result<(), Self::Error> {
+        self.release(memory);
 
         Ok(())
     }
@@ -218,7 +353,9 @@ impl PrimitiveSet for DynamicPrimitiveSet<'_, '_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::rc::Rc;
     use any_fn::{r#fn, value, Ref};
+    use core::cell::Cell;
 
     const HEAP_SIZE: usize = 1 << 8;
 
@@ -275,7 +412,7 @@ mod tests {
         let cons = memory
             .allocate(
                 Number::from_i64(index as _).into(),
-                memory.null().set_tag(Type::Foreign as _).into(),
+                DynamicPrimitiveSet::foreign_tag(&memory).into(),
             )
             .unwrap();
         memory.push(cons.into()).unwrap();
@@ -286,6 +423,182 @@ mod tests {
         assert_eq!(primitive_set.find_free(), None);
     }
 
+    #[test]
+    fn convert_collections() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut functions = [r#fn(|values: Vec<i64>| {
+            values
+                .into_iter()
+                .map(|value| value * 2)
+                .collect::<Vec<_>>()
+        })];
+        let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        memory.set_stack(memory.null());
+        let value = vec![1i64, 2, 3].into_scheme(&mut memory).unwrap();
+        memory.push(value).unwrap();
+
+        primitive_set.operate(&mut memory, 0).unwrap();
+
+        let value = memory.pop();
+
+        assert_eq!(Vec::<i64>::from_scheme(&memory, value), Some(vec![2, 4, 6]));
+    }
+
+    #[test]
+    fn convert_tuple() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut functions = [r#fn(|(x, y): (i64, char)| (String::from(y), x + 1))];
+        let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        memory.set_stack(memory.null());
+        let value = (41i64, 'a').into_scheme(&mut memory).unwrap();
+        memory.push(value).unwrap();
+
+        primitive_set.operate(&mut memory, 0).unwrap();
+
+        let value = memory.pop();
+
+        assert_eq!(
+            <(String, i64)>::from_scheme(&memory, value),
+            Some(("a".into(), 42))
+        );
+    }
+
+    #[test]
+    fn convert_byte_vector() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut functions = [r#fn(|bytes: Vec<u8>| {
+            bytes.into_iter().rev().collect::<Vec<_>>()
+        })];
+        let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        memory.set_stack(memory.null());
+        let value = vec![1u8, 2, 3].into_scheme(&mut memory).unwrap();
+        memory.push(value).unwrap();
+
+        primitive_set.operate(&mut memory, 0).unwrap();
+
+        let value = memory.pop();
+
+        assert_eq!(memory.cdr_value(value).tag(), Type::ByteVector as _);
+        assert_eq!(Vec::<u8>::from_scheme(&memory, value), Some(vec![3, 2, 1]));
+    }
+
+    mod error {
+        use super::*;
+        use core::fmt::{self, Formatter};
+
+        #[derive(Debug)]
+        struct FooError;
+
+        impl Display for FooError {
+            fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+                write!(formatter, "foo")
+            }
+        }
+
+        fn initialize(memory: &mut Memory, handler: bool) {
+            memory.set_stack(memory.null());
+            let code = memory
+                .cons(Number::from_i64(0).into(), memory.null())
+                .unwrap();
+            memory.set_code(code);
+
+            let environment = if handler {
+                memory
+                    .allocate(
+                        Number::from_i64(0).into(),
+                        memory.null().set_tag(Type::Procedure as _).into(),
+                    )
+                    .unwrap()
+            } else {
+                memory.null()
+            };
+            let procedure = memory
+                .allocate(
+                    Number::from_i64(0).into(),
+                    environment.set_tag(Type::Procedure as _).into(),
+                )
+                .unwrap();
+            memory.set_register(procedure);
+        }
+
+        #[test]
+        fn raise() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Err::<i64, _>(String::from("foo")))];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            initialize(&mut memory, true);
+
+            primitive_set.operate(&mut memory, 0).unwrap();
+
+            let message = memory.pop();
+
+            assert_eq!(String::from_scheme(&memory, message), Some("foo".into()));
+
+            let call = memory.cdr(memory.code()).assume_cons();
+            let operand = memory.car(call).assume_cons();
+
+            assert_eq!(
+                memory.car(operand),
+                memory.cdr(memory.register()).set_tag(Type::Pair as _)
+            );
+        }
+
+        #[test]
+        fn raise_without_handler() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Err::<i64, _>("foo"))];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            initialize(&mut memory, false);
+
+            assert!(matches!(
+                primitive_set.operate(&mut memory, 0),
+                Err(DynamicError::Function(message)) if message == "foo"
+            ));
+        }
+
+        #[test]
+        fn raise_registered_error() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Err::<i64, _>(FooError))];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            primitive_set.register_error_type::<FooError>();
+            initialize(&mut memory, false);
+
+            assert!(matches!(
+                primitive_set.operate(&mut memory, 0),
+                Err(DynamicError::Function(message)) if message == "foo"
+            ));
+        }
+
+        #[test]
+        fn keep_unregistered_error() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Err::<i64, _>(FooError))];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            initialize(&mut memory, false);
+
+            primitive_set.operate(&mut memory, 0).unwrap();
+
+            let value = memory.pop();
+
+            assert_eq!(DynamicPrimitiveSet::foreign_index(&memory, value), Some(0));
+        }
+    }
+
     mod garbage_collection {
         use super::*;
 
@@ -294,7 +607,9 @@ mod tests {
             let mut heap = [Default::default(); HEAP_SIZE];
             let mut primitive_set = DynamicPrimitiveSet::new(&mut []);
 
-            primitive_set.collect_garbages(&Memory::new(&mut heap).unwrap());
+            primitive_set
+                .collect_garbages(&Memory::new(&mut heap).unwrap())
+                .unwrap();
         }
 
         #[test]
@@ -310,7 +625,7 @@ mod tests {
 
             invalidate_foreign_values(&mut memory);
 
-            primitive_set.collect_garbages(&memory);
+            primitive_set.collect_garbages(&memory).unwrap();
 
             assert_eq!(primitive_set.find_free(), Some(0));
         }
@@ -326,9 +641,83 @@ mod tests {
 
             assert_eq!(primitive_set.find_free(), None);
 
-            primitive_set.collect_garbages(&memory);
+            primitive_set.collect_garbages(&memory).unwrap();
 
             assert_eq!(primitive_set.find_free(), None);
         }
+
+        #[test]
+        fn release_after_garbage_collection() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Foo { bar: 42 })];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            primitive_set.operate(&mut memory, 0).unwrap();
+            memory.pop();
+            memory.collect_garbages(None).unwrap();
+
+            primitive_set.collect_garbages(&memory).unwrap();
+
+            assert_eq!(primitive_set.find_free(), Some(0));
+        }
+
+        #[test]
+        fn keep_after_garbage_collection() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Foo { bar: 42 })];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            primitive_set.operate(&mut memory, 0).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            primitive_set.collect_garbages(&memory).unwrap();
+
+            assert_eq!(primitive_set.find_free(), None);
+        }
+
+        #[test]
+        fn finalize() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Foo { bar: 42 })];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+            let count = Rc::new(Cell::new(0));
+
+            primitive_set.register_finalizer({
+                let count = count.clone();
+                move |foo: Foo| count.set(count.get() + foo.bar())
+            });
+
+            primitive_set.operate(&mut memory, 0).unwrap();
+            memory.pop();
+            memory.collect_garbages(None).unwrap();
+
+            assert_eq!(count.get(), 0);
+
+            primitive_set.collect_garbages(&memory).unwrap();
+
+            assert_eq!(count.get(), 42);
+        }
+
+        #[test]
+        fn finalize_on_drop() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut functions = [r#fn(|| Foo { bar: 42 })];
+            let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+            let mut memory = Memory::new(&mut heap).unwrap();
+            let count = Rc::new(Cell::new(0));
+
+            primitive_set.register_finalizer({
+                let count = count.clone();
+                move |foo: Foo| count.set(count.get() + foo.bar())
+            });
+
+            primitive_set.operate(&mut memory, 0).unwrap();
+            drop(primitive_set);
+
+            assert_eq!(count.get(), 42);
+        }
     }
 }
@@ -1,5 +1,11 @@
 use super::DynamicError;
-use alloc::string::String;
+use crate::derive::push_element;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
 use stak_vm::{Memory, Number, Type, Value};
 
 /// A trait to convert Rust values from and into Scheme values.
@@ -9,11 +15,25 @@ pub trait SchemeValue: Sized {
 
     /// Converts a Rust value into a Scheme value.
     fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError>;
+
+    /// Converts a vector of Rust values into a Scheme value.
+    ///
+    /// It converts a vector into a list by default.
+    #[doc(hidden)]
+    fn vec_into_scheme(values: Vec<Self>, memory: &mut Memory) -> Result<Value, DynamicError> {
+        memory.push(memory.null().into())?;
+
+        for value in values.into_iter().rev() {
+            push_element(memory, value)?;
+        }
+
+        Ok(memory.pop())
+    }
 }
 
 impl SchemeValue for bool {
     fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
-        Some(value == memory.boolean(false).into())
+        Some(value != memory.boolean(false).into())
     }
 
     fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
@@ -25,7 +45,7 @@ macro_rules! implement_integer {
     ($type:ty) => {
         impl SchemeValue for $type {
             fn from_scheme(_memory: &Memory, value: Value) -> Option<Self> {
-                Some(value.assume_number().to_i64() as _)
+                Some(value.to_number()?.to_i64() as _)
             }
 
             fn into_scheme(self, _memory: &mut Memory) -> Result<Value, DynamicError> {
@@ -36,7 +56,6 @@ macro_rules! implement_integer {
 }
 
 implement_integer!(i8);
-implement_integer!(u8);
 implement_integer!(u16);
 implement_integer!(i16);
 implement_integer!(i32);
@@ -46,11 +65,38 @@ implement_integer!(u64);
 implement_integer!(isize);
 implement_integer!(usize);
 
+/// A byte is converted into an integer in Scheme. A vector of bytes is
+/// converted into a bytevector.
+impl SchemeValue for u8 {
+    fn from_scheme(_memory: &Memory, value: Value) -> Option<Self> {
+        Some(value.to_number()?.to_i64() as _)
+    }
+
+    fn into_scheme(self, _memory: &mut Memory) -> Result<Value, DynamicError> {
+        Ok(Number::from_i64(self as _).into())
+    }
+
+    fn vec_into_scheme(values: Vec<Self>, memory: &mut Memory) -> Result<Value, DynamicError> {
+        let mut list = memory.null();
+
+        for &byte in values.iter().rev() {
+            list = memory.cons(Number::from_i64(byte as _).into(), list)?;
+        }
+
+        Ok(memory
+            .allocate(
+                Number::from_i64(values.len() as _).into(),
+                list.set_tag(Type::ByteVector as _).into(),
+            )?
+            .into())
+    }
+}
+
 macro_rules! implement_float {
     ($type:ty) => {
         impl SchemeValue for $type {
             fn from_scheme(_memory: &Memory, value: Value) -> Option<Self> {
-                Some(value.assume_number().to_f64() as _)
+                Some(value.to_number()?.to_f64() as _)
             }
 
             fn into_scheme(self, _memory: &mut Memory) -> Result<Value, DynamicError> {
@@ -63,17 +109,41 @@ macro_rules! implement_float {
 implement_float!(f32);
 implement_float!(f64);
 
+impl SchemeValue for char {
+    fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
+        let cons = value.to_cons()?;
+
+        if memory.cdr(cons).tag() != Type::Character as _ {
+            return None;
+        }
+
+        Self::from_u32(memory.car(cons).to_number()?.to_i64() as _)
+    }
+
+    fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
+        Ok(memory
+            .allocate(
+                Number::from_i64(self as _).into(),
+                memory.null().set_tag(Type::Character as _).into(),
+            )?
+            .into())
+    }
+}
+
 impl SchemeValue for String {
     fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
-        let cons = value.assume_cons();
+        let cons = value.to_cons()?;
+
+        if memory.cdr(cons).tag() != Type::String as _ {
+            return None;
+        }
+
         let mut string = Self::with_capacity(memory.car(cons).assume_number().to_i64() as _);
         let mut cons = memory.cdr(cons).assume_cons();
 
         while cons != memory.null() {
-            string.push(char::from_u32(
-                memory.car(cons).assume_number().to_i64() as _
-            )?);
-            cons = memory.cdr(cons).assume_cons();
+            string.push(char::from_u32(memory.car(cons).to_number()?.to_i64() as _)?);
+            cons = memory.cdr(cons).to_cons()?;
         }
 
         Some(string)
@@ -97,9 +167,247 @@ impl SchemeValue for String {
     }
 }
 
+/// A vector is converted into a list in Scheme except that a vector of bytes is
+/// converted into a bytevector. It can be also converted from a list, a vector
+/// or a bytevector in Scheme.
+impl<T: SchemeValue> SchemeValue for Vec<T> {
+    fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
+        let cons = value.to_cons()?;
+        let mut list = if [Type::Vector, Type::ByteVector]
+            .into_iter()
+            .any(|r#type| memory.cdr(cons).tag() == r#type as _)
+        {
+            memory.cdr(cons).to_cons()?
+        } else {
+            cons
+        };
+        let mut values = Self::new();
+
+        while list != memory.null() {
+            if memory.cdr(list).tag() != Type::Pair as _ {
+                return None;
+            }
+
+            values.push(T::from_scheme(memory, memory.car(list))?);
+            list = memory.cdr(list).to_cons()?;
+        }
+
+        Some(values)
+    }
+
+    fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
+        T::vec_into_scheme(self, memory)
+    }
+}
+
+/// An option is converted into `#f` or a value in Scheme.
+impl<T: SchemeValue> SchemeValue for Option<T> {
+    fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
+        if value == memory.boolean(false).into() {
+            Some(None)
+        } else {
+            T::from_scheme(memory, value).map(Some)
+        }
+    }
+
+    fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
+        if let Some(value) = self {
+            value.into_scheme(memory)
+        } else {
+            Ok(memory.boolean(false).into())
+        }
+    }
+}
+
+/// A tuple of two values is converted into a pair in Scheme.
+impl<T: SchemeValue, U: SchemeValue> SchemeValue for (T, U) {
+    fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
+        let cons = value.to_cons()?;
+
+        if cons == memory.null() || memory.cdr(cons).tag() != Type::Pair as _ {
+            return None;
+        }
+
+        Some((
+            T::from_scheme(memory, memory.car(cons))?,
+            U::from_scheme(memory, memory.cdr(cons).set_tag(Type::Pair as _))?,
+        ))
+    }
+
+    fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
+        let car = self.0.into_scheme(memory)?;
+        memory.push(car)?;
+        let cdr = self.1.into_scheme(memory)?;
+        let car = memory.pop();
+
+        Ok(memory.allocate(car, cdr.set_tag(Type::Pair as _))?.into())
+    }
+}
+
+/// A map is converted into an association list in Scheme.
+impl<K: SchemeValue + Ord, V: SchemeValue> SchemeValue for BTreeMap<K, V> {
+    fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
+        Some(
+            Vec::<(K, V)>::from_scheme(memory, value)?
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
+        memory.push(memory.null().into())?;
+
+        for entry in self.into_iter().rev() {
+            push_element(memory, entry)?;
+        }
+
+        Ok(memory.pop())
+    }
+}
+
+/// A result is converted into a value on success.
+///
+/// On failure, it is converted into a [`DynamicError::Function`] error,
+/// which procedures defined by `define-rust` in Scheme raise as an error
+/// object.
+impl<T: SchemeValue, E: Display> SchemeValue for Result<T, E> {
+    fn from_scheme(memory: &Memory, value: Value) -> Option<Self> {
+        T::from_scheme(memory, value).map(Ok)
+    }
+
+    fn into_scheme(self, memory: &mut Memory) -> Result<Value, DynamicError> {
+        match self {
+            Ok(value) => value.into_scheme(memory),
+            Err(error) => Err(DynamicError::Function(error.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+
+    const HEAP_SIZE: usize = 1 << 10;
+
+    fn round_trip<T: SchemeValue>(value: T) -> Option<T> {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        let value = value.into_scheme(&mut memory).unwrap();
+
+        T::from_scheme(&memory, value)
+    }
+
+    #[test]
+    fn boolean() {
+        assert_eq!(round_trip(false), Some(false));
+        assert_eq!(round_trip(true), Some(true));
+    }
+
+    #[test]
+    fn character() {
+        assert_eq!(round_trip('a'), Some('a'));
+        assert_eq!(round_trip('😄'), Some('😄'));
+    }
+
+    #[test]
+    fn byte_vector() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        let value = vec![1u8, 2, 3].into_scheme(&mut memory).unwrap();
+
+        assert_eq!(memory.cdr_value(value).tag(), Type::ByteVector as _);
+        assert_eq!(Vec::<u8>::from_scheme(&memory, value), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn option() {
+        assert_eq!(round_trip(None::<i64>), Some(None));
+        assert_eq!(round_trip(Some(42i64)), Some(Some(42)));
+    }
+
+    #[test]
+    fn tuple() {
+        assert_eq!(
+            round_trip((42i64, String::from("foo"))),
+            Some((42, "foo".into()))
+        );
+    }
+
+    #[test]
+    fn result() {
+        assert_eq!(round_trip(Ok::<i64, String>(42)), Some(Ok(42)));
+    }
+
+    #[test]
+    fn error() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        assert!(matches!(
+            Err::<i64, _>("foo").into_scheme(&mut memory),
+            Err(DynamicError::Function(message)) if message == "foo"
+        ));
+    }
+
+    #[test]
+    fn map() {
+        let map = BTreeMap::from([(String::from("foo"), 1i64), (String::from("bar"), 2)]);
+
+        assert_eq!(round_trip(map.clone()), Some(map));
+    }
+
+    mod vector {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            assert_eq!(round_trip(Vec::<i64>::new()), Some(vec![]));
+        }
+
+        #[test]
+        fn numbers() {
+            assert_eq!(round_trip(vec![1i64, 2, 3]), Some(vec![1, 2, 3]));
+        }
+
+        #[test]
+        fn strings() {
+            assert_eq!(
+                round_trip(vec![String::from("foo"), String::from("bar")]),
+                Some(vec!["foo".into(), "bar".into()])
+            );
+        }
+
+        #[test]
+        fn nested() {
+            assert_eq!(
+                round_trip(vec![vec![1i64], vec![], vec![2, 3]]),
+                Some(vec![vec![1], vec![], vec![2, 3]])
+            );
+        }
+
+        #[test]
+        fn from_byte_vector() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            let value = vec![1u8, 2, 3].into_scheme(&mut memory).unwrap();
+
+            assert_eq!(Vec::<i64>::from_scheme(&memory, value), Some(vec![1, 2, 3]));
+        }
+
+        #[test]
+        fn from_invalid_value() {
+            let mut heap = [Default::default(); HEAP_SIZE];
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            let value = String::from("foo").into_scheme(&mut memory).unwrap();
+
+            assert_eq!(Vec::<i64>::from_scheme(&memory, value), None);
+        }
+    }
 
     mod string {
         use super::*;
@@ -0,0 +1,226 @@
+//! Derive macros for dynamic primitives.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Index};
+
+/// Derives the `SchemeValue` trait of the
+/// [`stak_dynamic`](https://docs.rs/stak-dynamic) crate.
+///
+/// - Structs with named fields are converted into association lists from
+///   symbols of field names to field values.
+/// - Tuple structs are converted into lists of field values.
+/// - Enums of unit variants are converted into symbols of variant names.
+///
+/// Field and variant names are converted into kebab case in Scheme (e.g.
+/// `first_name` into `first-name` and `DarkRed` into `dark-red`.)
+///
+/// # Examples
+///
+/// ```rust
+/// use stak_dynamic_macro::SchemeValue;
+///
+/// #[derive(SchemeValue)]
+/// struct Person {
+///     name: String,
+///     age: usize,
+/// }
+///
+/// #[derive(SchemeValue)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+/// ```
+#[proc_macro_derive(SchemeValue)]
+pub fn derive_scheme_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    generate(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn generate(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let (from_scheme, into_scheme) = match &input.data {
+        Data::Struct(data) => generate_struct(&data.fields),
+        Data::Enum(data) => {
+            let mut variants = vec![];
+
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "only unit variants are supported",
+                    ));
+                }
+
+                variants.push(&variant.ident);
+            }
+
+            generate_enum(&variants)
+        }
+        Data::Union(_) => return Err(Error::new(Span::call_site(), "unions are not supported")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::stak_dynamic::SchemeValue for #name #type_generics #where_clause {
+            fn from_scheme(
+                memory: &::stak_dynamic::__private::stak_vm::Memory,
+                value: ::stak_dynamic::__private::stak_vm::Value,
+            ) -> Option<Self> {
+                #from_scheme
+            }
+
+            fn into_scheme(
+                self,
+                memory: &mut ::stak_dynamic::__private::stak_vm::Memory,
+            ) -> Result<::stak_dynamic::__private::stak_vm::Value, ::stak_dynamic::DynamicError> {
+                #into_scheme
+            }
+        }
+    })
+}
+
+fn generate_struct(fields: &Fields) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .flat_map(|field| field.ident.as_ref())
+                .collect::<Vec<_>>();
+            let names = fields
+                .iter()
+                .map(|field| convert_field_name(&field.to_string()))
+                .collect::<Vec<_>>();
+            let reversed_fields = fields.iter().rev();
+            let reversed_names = names.iter().rev();
+
+            (
+                quote! {
+                    Some(Self {
+                        #(#fields: ::stak_dynamic::SchemeValue::from_scheme(
+                            memory,
+                            ::stak_dynamic::__private::field(memory, value, #names)?,
+                        )?,)*
+                    })
+                },
+                quote! {
+                    memory.push(memory.null().into())?;
+                    #(::stak_dynamic::__private::push_field(
+                        memory,
+                        #reversed_names,
+                        self.#reversed_fields,
+                    )?;)*
+                    Ok(memory.pop())
+                },
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let indices = (0..fields.unnamed.len()).collect::<Vec<_>>();
+            let reversed_fields = indices.iter().rev().map(|&index| Index::from(index));
+
+            (
+                quote! {
+                    Some(Self(
+                        #(::stak_dynamic::SchemeValue::from_scheme(
+                            memory,
+                            ::stak_dynamic::__private::element(memory, value, #indices)?,
+                        )?,)*
+                    ))
+                },
+                quote! {
+                    memory.push(memory.null().into())?;
+                    #(::stak_dynamic::__private::push_element(memory, self.#reversed_fields)?;)*
+                    Ok(memory.pop())
+                },
+            )
+        }
+        Fields::Unit => (
+            quote! {
+                (value == memory.null().into()).then_some(Self)
+            },
+            quote! {
+                Ok(memory.null().into())
+            },
+        ),
+    }
+}
+
+fn generate_enum(variants: &[&Ident]) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let names = variants
+        .iter()
+        .map(|variant| convert_variant_name(&variant.to_string()))
+        .collect::<Vec<_>>();
+
+    (
+        quote! {
+            #(if ::stak_dynamic::__private::is_symbol(memory, value, #names) {
+                return Some(Self::#variants);
+            })*
+
+            None
+        },
+        quote! {
+            ::stak_dynamic::__private::symbol(
+                memory,
+                match self {
+                    #(Self::#variants => #names,)*
+                },
+            )
+        },
+    )
+}
+
+fn convert_field_name(name: &str) -> String {
+    name.trim_start_matches("r#").replace('_', "-")
+}
+
+fn convert_variant_name(name: &str) -> String {
+    let characters = name.chars().collect::<Vec<_>>();
+    let mut string = String::new();
+
+    for (index, &character) in characters.iter().enumerate() {
+        // Split words before an uppercase letter following a lowercase one, or
+        // before the last uppercase letter of an acronym followed by a word.
+        if index > 0
+            && character.is_uppercase()
+            && (!characters[index - 1].is_uppercase()
+                || characters
+                    .get(index + 1)
+                    .is_some_and(|character| character.is_lowercase()))
+        {
+            string.push('-');
+        }
+
+        string.extend(character.to_lowercase());
+    }
+
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_field_names() {
+        assert_eq!(convert_field_name("foo"), "foo");
+        assert_eq!(convert_field_name("foo_bar"), "foo-bar");
+        assert_eq!(convert_field_name("r#type"), "type");
+    }
+
+    #[test]
+    fn convert_variant_names() {
+        assert_eq!(convert_variant_name("Foo"), "foo");
+        assert_eq!(convert_variant_name("FooBar"), "foo-bar");
+        assert_eq!(convert_variant_name("HTTPServer"), "http-server");
+        assert_eq!(convert_variant_name("ServerHTTP"), "server-http");
+        assert_eq!(convert_variant_name("Utf8"), "utf8");
+    }
+}
@@ -0,0 +1,148 @@
+#![expect(missing_docs)]
+
+use any_fn::r#fn;
+use stak_device::FixedBufferDevice;
+use stak_dynamic::{DynamicPrimitiveSet, SchemeValue};
+use stak_dynamic_macro::SchemeValue;
+use stak_engine::Engine;
+use stak_file::VoidFileSystem;
+use stak_macro::compile_r7rs;
+use stak_module::StaticModule;
+use stak_process_context::VoidProcessContext;
+use stak_time::VoidClock;
+use stak_vm::{Memory, PrimitiveSet};
+
+const HEAP_SIZE: usize = 1 << 16;
+const BUFFER_SIZE: usize = 1 << 10;
+
+#[derive(Clone, Debug, PartialEq, SchemeValue)]
+struct Person {
+    name: String,
+    age: usize,
+    favorite_color: Color,
+}
+
+#[derive(Clone, Debug, PartialEq, SchemeValue)]
+struct Point(i64, i64);
+
+#[derive(Clone, Debug, PartialEq, SchemeValue)]
+struct Empty;
+
+#[derive(Clone, Copy, Debug, PartialEq, SchemeValue)]
+enum Color {
+    Red,
+    DarkBlue,
+}
+
+fn round_trip<T: SchemeValue + Clone + 'static>(value: T) -> T {
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [r#fn(|value: T| value)];
+    let mut primitive_set = DynamicPrimitiveSet::new(&mut functions);
+    let mut memory = Memory::new(&mut heap).unwrap();
+
+    primitive_set.register_type::<T>();
+    memory.set_stack(memory.null());
+
+    let value = value.into_scheme(&mut memory).unwrap();
+    memory.push(value).unwrap();
+    primitive_set.operate(&mut memory, 0).unwrap();
+    let value = memory.pop();
+
+    T::from_scheme(&memory, value).unwrap()
+}
+
+#[test]
+fn round_trip_struct() {
+    let person = Person {
+        name: "Alice".into(),
+        age: 42,
+        favorite_color: Color::DarkBlue,
+    };
+
+    assert_eq!(round_trip(person.clone()), person);
+}
+
+#[test]
+fn round_trip_tuple_struct() {
+    assert_eq!(round_trip(Point(1, -2)), Point(1, -2));
+}
+
+#[test]
+fn round_trip_unit_struct() {
+    assert_eq!(round_trip(Empty), Empty);
+}
+
+#[test]
+fn round_trip_enum() {
+    assert_eq!(round_trip(Color::Red), Color::Red);
+    assert_eq!(round_trip(Color::DarkBlue), Color::DarkBlue);
+}
+
+#[test]
+fn reject_invalid_values() {
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut memory = Memory::new(&mut heap).unwrap();
+
+    let value = Color::Red.into_scheme(&mut memory).unwrap();
+
+    assert_eq!(Person::from_scheme(&memory, value), None);
+    assert_eq!(Person::from_scheme(&memory, memory.null().into()), None);
+    assert_eq!(Color::from_scheme(&memory, memory.null().into()), None);
+}
+
+#[test]
+fn use_in_scheme() {
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base) (scheme write) (stak rust))
+
+        (define-rust make-person person-older)
+
+        (define person (make-person "Bob" 17))
+
+        (write (cdr (assq 'name person)))
+        (write (cdr (assq 'age person)))
+        (write (eq? (cdr (assq 'favorite-color person)) 'red))
+
+        (let ((person
+                (person-older
+                  (list
+                    (cons 'name "Carol")
+                    (cons 'age 30)
+                    (cons 'favorite-color 'dark-blue)))))
+          (write (cdr (assq 'age person)))
+          (write (cdr (assq 'favorite-color person)))
+          (write
+            (eq?
+              (cdr (assq 'favorite-color person))
+              (string->symbol "dark-blue"))))
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [
+        r#fn(|name: String, age: usize| Person {
+            name,
+            age,
+            favorite_color: Color::Red,
+        }),
+        r#fn(|person: Person| Person {
+            age: person.age + 1,
+            ..person
+        }),
+    ];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    engine.register_type::<Person>();
+    engine.run(&MODULE).unwrap();
+
+    assert_eq!(engine.device().output(), b"\"Bob\"17#t31dark-blue#t");
+}
@@ -1,32 +1,145 @@
-use crate::{primitive_set::EnginePrimitiveSet, EngineError};
+use crate::{
+    primitive_set::{DefaultDevice, EnginePrimitiveSet},
+    EngineError,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use any_fn::AnyFn;
+use core::{any::Any, fmt::Display};
+use stak_device::Device;
 use stak_dynamic::SchemeValue;
+use stak_file::{FileSystem, VoidFileSystem};
 use stak_module::Module;
-use stak_vm::{Error, Value, Vm};
+use stak_process_context::{ProcessContext, VoidProcessContext};
+use stak_time::{Clock, VoidClock};
+use stak_vm::{Cons, Error, Number, Status, Type, Value, Vm};
 
 /// A scripting engine.
-pub struct Engine<'a, 'b> {
-    vm: Vm<'a, EnginePrimitiveSet<'a, 'b>>,
+pub struct Engine<
+    'a,
+    'b,
+    D: Device = DefaultDevice,
+    F: FileSystem = VoidFileSystem,
+    P: ProcessContext = VoidProcessContext,
+    C: Clock = VoidClock,
+> {
+    vm: Vm<'a, EnginePrimitiveSet<'a, 'b, D, F, P, C>>,
+    // Procedures found by names. They are valid until the next garbage
+    // collection as they move on it.
+    procedures: Vec<(String, Value)>,
+    garbage_collection_count: usize,
+    backtrace: Vec<String>,
 }
 
 impl<'a, 'b> Engine<'a, 'b> {
     /// Creates a scripting engine.
     pub fn new(heap: &'a mut [Value], functions: &'a mut [AnyFn<'b>]) -> Result<Self, Error> {
+        Self::with_system(
+            heap,
+            functions,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+    }
+}
+
+impl<'a, 'b, D: Device, F: FileSystem, P: ProcessContext, C: Clock> Engine<'a, 'b, D, F, P, C> {
+    /// Creates a scripting engine with a device, a file system, a process
+    /// context, and a clock.
+    pub fn with_system(
+        heap: &'a mut [Value],
+        functions: &'a mut [AnyFn<'b>],
+        device: D,
+        file_system: F,
+        process_context: P,
+        clock: C,
+    ) -> Result<Self, Error> {
         Ok(Self {
-            vm: Vm::new(heap, EnginePrimitiveSet::new(functions))?,
+            vm: Vm::new(
+                heap,
+                EnginePrimitiveSet::new(functions, device, file_system, process_context, clock),
+            )?
+            .with_retained_program(),
+            procedures: Vec::new(),
+            garbage_collection_count: 0,
+            backtrace: Vec::new(),
         })
     }
 
+    /// Returns a reference to a device.
+    pub fn device(&self) -> &D {
+        self.vm.primitive_set().device()
+    }
+
+    /// Returns a mutable reference to a device.
+    pub fn device_mut(&mut self) -> &mut D {
+        self.vm.primitive_set_mut().device_mut()
+    }
+
+    /// Returns a backtrace of procedure names from the innermost call on the
+    /// last runtime error.
+    pub fn backtrace(&self) -> &[String] {
+        &self.backtrace
+    }
+
     /// Runs a module.
+    ///
+    /// It returns [`EngineError::Suspended`] if the module is suspended before
+    /// it finishes.
     pub fn run<'c>(&mut self, module: &'c impl Module<'c>) -> Result<(), EngineError> {
+        self.procedures.clear();
         self.vm.initialize(module.bytecode().iter().copied())?;
-        self.vm.run()
+
+        match self
+            .vm
+            .run()
+            .map_err(|error| self.capture_backtrace(error))?
+        {
+            Status::Finished => Ok(()),
+            Status::Suspended | Status::OutOfFuel => Err(EngineError::Suspended),
+        }
+    }
+
+    /// Calls a procedure defined as a global variable in a module.
+    ///
+    /// The module must be run by [`Engine::run`] beforehand. Arguments are
+    /// marshalled into Scheme values in the same way as return values of Rust
+    /// functions, and a return value is marshalled back by [`SchemeValue`].
+    pub fn call<T: SchemeValue>(
+        &mut self,
+        name: &str,
+        arguments: impl IntoIterator<Item = any_fn::Value>,
+    ) -> Result<T, EngineError> {
+        let procedure = self.find_procedure(name)?;
+        let value = self
+            .vm
+            .apply(procedure, |memory, primitive_set| {
+                let mut count = 0;
+
+                for argument in arguments {
+                    let value = primitive_set
+                        .dynamic_mut()
+                        .convert_into_scheme(memory, argument)?;
+                    memory.push(value)?;
+                    count += 1;
+                }
+
+                Ok(count)
+            })
+            .map_err(|error| self.capture_backtrace(error))?;
+
+        T::from_scheme(self.vm.memory(), value).ok_or(EngineError::ReturnValue)
     }
 
     /// Registers a type compatible between Scheme and Rust.
     ///
-    /// We register all types that this crate implements [`SchemeValue`] for to
-    /// the engines by default.
+    /// We register all scalar types that this crate implements
+    /// [`SchemeValue`] for and common collections of them to the engines by
+    /// default.
     ///
     /// For more information, see
     /// [`DynamicPrimitiveSet`][stak_dynamic::DynamicPrimitiveSet].
@@ -36,4 +149,95 @@ impl<'a, 'b> Engine<'a, 'b> {
             .dynamic_mut()
             .register_type::<T>()
     }
+
+    /// Registers an error type of fallible functions.
+    ///
+    /// For more information, see
+    /// [`DynamicPrimitiveSet`][stak_dynamic::DynamicPrimitiveSet].
+    pub fn register_error_type<E: Display + 'static>(&mut self) {
+        self.vm
+            .primitive_set_mut()
+            .dynamic_mut()
+            .register_error_type::<E>()
+    }
+
+    /// Registers a finalizer for foreign values of a type.
+    ///
+    /// For more information, see
+    /// [`DynamicPrimitiveSet`][stak_dynamic::DynamicPrimitiveSet].
+    pub fn register_finalizer<T: Any>(&mut self, finalize: impl FnMut(T) + 'b) {
+        self.vm
+            .primitive_set_mut()
+            .dynamic_mut()
+            .register_finalizer(finalize)
+    }
+
+    fn capture_backtrace(&mut self, error: EngineError) -> EngineError {
+        self.backtrace = self.vm.backtrace().map(|frame| frame.to_string()).collect();
+
+        error
+    }
+
+    fn find_procedure(&mut self, name: &str) -> Result<Value, EngineError> {
+        let count = self.vm.memory().garbage_collection_count();
+
+        if count != self.garbage_collection_count {
+            self.procedures.clear();
+            self.garbage_collection_count = count;
+        }
+
+        if let Some((_, procedure)) = self.procedures.iter().find(|(other, _)| other == name) {
+            return Ok(*procedure);
+        }
+
+        let procedure = self.lookup_procedure(name)?;
+        self.procedures.push((name.into(), procedure));
+
+        Ok(procedure)
+    }
+
+    // Looks up a procedure in a symbol table kept in a `car` of `#t`.
+    fn lookup_procedure(&self, name: &str) -> Result<Value, EngineError> {
+        let memory = self.vm.memory();
+        let mut list = memory.car(memory.boolean(true));
+
+        while let Some(cons) = list.to_cons() {
+            if cons == memory.null() {
+                break;
+            }
+
+            if let Some(symbol) = memory.car(cons).to_cons() {
+                let value = memory.car(symbol);
+
+                if memory.cdr(symbol).tag() == Type::Symbol as _
+                    && self.equal_name(symbol, name)
+                    && value.is_cons()
+                    && memory.cdr_value(value).tag() == Type::Procedure as _
+                {
+                    return Ok(value);
+                }
+            }
+
+            list = memory.cdr(cons);
+        }
+
+        Err(EngineError::ProcedureNotFound)
+    }
+
+    fn equal_name(&self, symbol: Cons, name: &str) -> bool {
+        let memory = self.vm.memory();
+        let mut string = memory.cdr_value(memory.cdr(symbol)).assume_cons();
+
+        for character in name.chars() {
+            if string == memory.null()
+                || memory.car(string) != Number::from_i64(character as _).into()
+            {
+                return false;
+            }
+
+            string = memory.cdr(string).assume_cons();
+        }
+
+        string == memory.null()
+    }
 }
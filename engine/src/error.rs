@@ -10,8 +10,14 @@ use stak_r7rs::SmallError;
 pub enum EngineError {
     /// A dynamic primitive error.
     Dynamic(DynamicError),
+    /// A procedure not found.
+    ProcedureNotFound,
+    /// A return value not convertible into a Rust value.
+    ReturnValue,
     /// An R7RS-small error.
     Small(SmallError),
+    /// A program suspended before it finishes.
+    Suspended,
     /// A virtual machine error.
     Vm(stak_vm::Error),
 }
@@ -40,7 +46,10 @@ impl Display for EngineError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
             Self::Dynamic(error) => write!(formatter, "{error}"),
+            Self::ProcedureNotFound => write!(formatter, "procedure not found"),
+            Self::ReturnValue => write!(formatter, "invalid return value"),
             Self::Small(error) => write!(formatter, "{error}"),
+            Self::Suspended => write!(formatter, "program suspended"),
             Self::Vm(error) => write!(formatter, "{error}"),
         }
     }
@@ -2,9 +2,12 @@
 
 #![no_std]
 
+extern crate alloc;
+
 mod engine;
 mod error;
 mod primitive_set;
 
 pub use engine::*;
 pub use error::*;
+pub use primitive_set::DefaultDevice;
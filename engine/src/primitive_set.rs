@@ -1,49 +1,75 @@
 use crate::EngineError;
 use any_fn::AnyFn;
 use cfg_elif::item;
+use stak_device::Device;
 use stak_dynamic::DynamicPrimitiveSet;
-use stak_file::VoidFileSystem;
-use stak_process_context::VoidProcessContext;
+use stak_file::{FileSystem, VoidFileSystem};
+use stak_process_context::{ProcessContext, VoidProcessContext};
 use stak_r7rs::SmallPrimitiveSet;
-use stak_time::VoidClock;
+use stak_time::{Clock, VoidClock};
 use stak_vm::{Memory, PrimitiveSet};
 
 const DYNAMIC_PRIMITIVE_OFFSET: usize = 1000;
 
 item::feature!(if ("std") {
-    type Device = stak_device::StdioDevice;
+    /// A default device.
+    pub type DefaultDevice = stak_device::StdioDevice;
 } else if ("libc") {
-    type Device = stak_device::LibcDevice;
+    /// A default device.
+    pub type DefaultDevice = stak_device::LibcDevice;
 } else {
-    type Device = stak_device::VoidDevice;
+    /// A default device.
+    pub type DefaultDevice = stak_device::VoidDevice;
 });
 
 /// A type check primitive set.
-pub struct EnginePrimitiveSet<'a, 'b> {
-    small: SmallPrimitiveSet<Device, VoidFileSystem, VoidProcessContext, VoidClock>,
+pub struct EnginePrimitiveSet<
+    'a,
+    'b,
+    D: Device = DefaultDevice,
+    F: FileSystem = VoidFileSystem,
+    P: ProcessContext = VoidProcessContext,
+    C: Clock = VoidClock,
+> {
+    small: SmallPrimitiveSet<D, F, P, C>,
     dynamic: DynamicPrimitiveSet<'a, 'b>,
 }
 
-impl<'a, 'b> EnginePrimitiveSet<'a, 'b> {
+impl<'a, 'b, D: Device, F: FileSystem, P: ProcessContext, C: Clock>
+    EnginePrimitiveSet<'a, 'b, D, F, P, C>
+{
     /// Creates a primitive set.
-    pub fn new(functions: &'a mut [AnyFn<'b>]) -> Self {
+    pub fn new(
+        functions: &'a mut [AnyFn<'b>],
+        device: D,
+        file_system: F,
+        process_context: P,
+        clock: C,
+    ) -> Self {
         Self {
-            small: SmallPrimitiveSet::new(
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-            ),
+            small: SmallPrimitiveSet::new(device, file_system, process_context, clock),
             dynamic: DynamicPrimitiveSet::new(functions),
         }
     }
 
+    /// Returns a reference to a device.
+    pub fn device(&self) -> &D {
+        self.small.device()
+    }
+
+    /// Returns a mutable reference to a device.
+    pub fn device_mut(&mut self) -> &mut D {
+        self.small.device_mut()
+    }
+
     pub(crate) fn dynamic_mut(&mut self) -> &mut DynamicPrimitiveSet<'a, 'b> {
         &mut self.dynamic
     }
 }
 
-impl PrimitiveSet for EnginePrimitiveSet<'_, '_> {
+impl<D: Device, F: FileSystem, P: ProcessContext, C: Clock> PrimitiveSet
+    for EnginePrimitiveSet<'_, '_, D, F, P, C>
+{
     type Error = EngineError;
 
     fn operate(&mut self, memory: &mut Memory, primitive: usize) -> Result<(), Self::Error> {
@@ -56,4 +82,11 @@ impl PrimitiveSet for EnginePrimitiveSet<'_, '_> {
 
         Ok(())
     }
+
+    fn collect_garbages(&mut self, memory: &Memory) -> Result<(), Self::Error> {
+        self.small.collect_garbages(memory)?;
+        self.dynamic.collect_garbages(memory)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,322 @@
+#![expect(missing_docs)]
+
+use any_fn::r#fn;
+use core::fmt::{self, Display, Formatter};
+use stak_device::{Device, FixedBufferDevice};
+use stak_engine::{Engine, EngineError};
+use stak_file::VoidFileSystem;
+use stak_macro::compile_r7rs;
+use stak_module::StaticModule;
+use stak_process_context::VoidProcessContext;
+use stak_time::VoidClock;
+use stak_vm::Error;
+
+const HEAP_SIZE: usize = 1 << 16;
+const BUFFER_SIZE: usize = 1 << 10;
+
+fn divide(x: i64, y: i64) -> Result<i64, String> {
+    if y == 0 {
+        Err("division by zero".into())
+    } else {
+        Ok(x / y)
+    }
+}
+
+#[test]
+fn raise_error() {
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base) (scheme write) (stak rust))
+
+        (define-rust divide)
+
+        (write (divide 6 3))
+
+        (write
+          (guard (error
+                  ((error-object? error)
+                    (error-object-message error)))
+            (divide 1 0)))
+
+        (write
+          (guard (error
+                  ((error-object? error)
+                    (error-object-message error)))
+            (apply divide '(1 0))))
+
+        (write (divide 8 2))
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [r#fn(divide)];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    engine.run(&MODULE).unwrap();
+
+    assert_eq!(
+        engine.device().output(),
+        b"2\"division by zero\"\"division by zero\"4"
+    );
+}
+
+#[test]
+fn raise_custom_error() {
+    #[derive(Debug)]
+    struct FooError;
+
+    impl Display for FooError {
+        fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+            write!(formatter, "foo")
+        }
+    }
+
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base) (scheme write) (stak rust))
+
+        (define-rust foo)
+
+        (write
+          (guard (error
+                  ((error-object? error)
+                    (error-object-message error)))
+            (foo)))
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [r#fn(|| Err::<i64, _>(FooError))];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    engine.register_error_type::<FooError>();
+    engine.run(&MODULE).unwrap();
+
+    assert_eq!(engine.device().output(), b"\"foo\"");
+}
+
+#[test]
+fn read_input_with_system() {
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base))
+
+        (write-u8 (+ (read-u8) 1))
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(b"A"),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    engine.run(&MODULE).unwrap();
+
+    assert_eq!(engine.device().output(), b"B");
+}
+
+#[test]
+fn write_output_through_device() {
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base))
+
+        (write-u8 65)
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    engine.run(&MODULE).unwrap();
+    engine.device_mut().write(b'B').unwrap();
+
+    assert_eq!(engine.device().output(), b"AB");
+}
+
+#[test]
+fn capture_backtrace() {
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base))
+
+        (define baz #f)
+
+        (define (bar)
+          (baz)
+          #f)
+
+        (define (foo)
+          (bar)
+          #f)
+
+        (foo)
+        #f
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        engine.run(&MODULE),
+        Err(EngineError::Vm(Error::ProcedureExpected))
+    ));
+    assert_eq!(engine.backtrace(), ["baz", "bar", "foo"]);
+}
+
+#[test]
+fn capture_backtrace_on_argument_count() {
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base))
+
+        (define (bar x) x)
+
+        (define (foo)
+          (bar 1 2)
+          #f)
+
+        (foo)
+        #f
+        "#
+    ));
+
+    let mut heap = [Default::default(); HEAP_SIZE];
+    let mut functions = [];
+    let mut engine = Engine::with_system(
+        &mut heap,
+        &mut functions,
+        FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+        VoidFileSystem::new(),
+        VoidProcessContext::new(),
+        VoidClock::new(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        engine.run(&MODULE),
+        Err(EngineError::Vm(Error::ArgumentCount))
+    ));
+    assert_eq!(engine.backtrace(), ["bar", "foo"]);
+}
+
+mod call {
+    use super::*;
+    use any_fn::value;
+
+    static MODULE: StaticModule = StaticModule::new(compile_r7rs!(
+        r#"
+        (import (scheme base))
+
+        (define (add x y)
+          (+ x y))
+
+        (define (fail)
+          (error "foo"))
+        "#
+    ));
+
+    fn run<'a>(
+        heap: &'a mut [stak_vm::Value],
+        functions: &'a mut [any_fn::AnyFn<'static>],
+    ) -> Engine<'a, 'static, FixedBufferDevice<BUFFER_SIZE, BUFFER_SIZE>> {
+        let mut engine = Engine::with_system(
+            heap,
+            functions,
+            FixedBufferDevice::<BUFFER_SIZE, BUFFER_SIZE>::new(&[]),
+            VoidFileSystem::new(),
+            VoidProcessContext::new(),
+            VoidClock::new(),
+        )
+        .unwrap();
+
+        engine.run(&MODULE).unwrap();
+
+        engine
+    }
+
+    #[test]
+    fn call_procedure() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut functions = [];
+        let mut engine = run(&mut heap, &mut functions);
+
+        for index in 0..3 {
+            assert_eq!(
+                engine
+                    .call::<i64>("add", [value(index), value(2i64)])
+                    .unwrap(),
+                index + 2
+            );
+        }
+    }
+
+    #[test]
+    fn call_missing_procedure() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut functions = [];
+        let mut engine = run(&mut heap, &mut functions);
+
+        assert!(matches!(
+            engine.call::<i64>("foo", []),
+            Err(EngineError::ProcedureNotFound)
+        ));
+    }
+
+    #[test]
+    fn call_failing_procedure() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut functions = [];
+        let mut engine = run(&mut heap, &mut functions);
+
+        assert!(engine.call::<i64>("fail", []).is_err());
+        assert_eq!(
+            engine
+                .call::<i64>("add", [value(1i64), value(2i64)])
+                .unwrap(),
+            3
+        );
+    }
+}
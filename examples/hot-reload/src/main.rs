@@ -67,7 +67,9 @@ fn run(
     )?;
 
     vm.initialize(bytecodes.iter().copied())?;
-    vm.run()
+    vm.run()?;
+
+    Ok(())
 }
 
 fn decode_buffer(buffer: Vec<u8>) -> response::Result<String> {
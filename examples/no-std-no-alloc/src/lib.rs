@@ -64,7 +64,9 @@ fn run_vm(
     )?;
 
     vm.initialize(bytecodes.iter().copied())?;
-    vm.run()
+    vm.run()?;
+
+    Ok(())
 }
 
 /// A fibonacci calculation error.
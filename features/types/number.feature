@@ -231,6 +231,48 @@ Feature: Number
       | (floor-remainder 5 3)      | 2     |
       | (floor-remainder -5 2)     | 1     |
       | (floor-remainder -5 -2)    | -1    |
+      | (floor-remainder 5 -2)     | -1    |
+      | (floor-quotient 6 2)       | 3     |
+      | (floor-quotient 7 2)       | 3     |
+      | (floor-quotient -7 2)      | -4    |
+      | (floor-quotient 7 -2)      | -4    |
+      | (floor-quotient -7 -2)     | 3     |
+      | (square 0)                 | 0     |
+      | (square 3)                 | 9     |
+      | (square -3)                | 9     |
+
+  Scenario Outline: Use division operators returning multiple values
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (let-values (((q r) (<procedure> <dividend> <divisor>)))
+        (write-u8 (if (and (= q <quotient>) (= r <remainder>)) 65 66)))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | procedure | dividend | divisor | quotient | remainder |
+      | floor/    | 5        | 2       | 2        | 1         |
+      | floor/    | -5       | 2       | -3       | 1         |
+      | floor/    | 5        | -2      | -3       | -1        |
+      | floor/    | -5       | -2      | 2        | -1        |
+      | truncate/ | 5        | 2       | 2        | 1         |
+      | truncate/ | -5       | 2       | -2       | -1        |
+      | truncate/ | 5        | -2      | -2       | 1         |
+      | truncate/ | -5       | -2      | 2        | -1        |
+
+  @float
+  Scenario: Square a floating point number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (= (square 1.5) 2.25) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
 
   Scenario: Calculate a multiplicative inverse
     Given a file named "main.scm" with:
@@ -242,6 +284,194 @@ Feature: Number
     When I successfully run `stak main.scm`
     Then the stdout should contain exactly "A"
 
+  Scenario Outline: Get a numerator and a denominator of an integer
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (= <expression> <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression       | value |
+      | (numerator 0)    | 0     |
+      | (numerator 6)    | 6     |
+      | (numerator -6)   | -6    |
+      | (denominator 0)  | 1     |
+      | (denominator 6)  | 1     |
+      | (denominator -6) | 1     |
+
+  @bignum
+  Scenario Outline: Use big integer literals
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (= <expression> <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression                        | value                    |
+      | (* 100000000000 100000000000)     | 10000000000000000000000  |
+      | (- (* 100000000000 100000000000)) | -10000000000000000000000 |
+      | (+ 4611686018427387903 1)         | 4611686018427387904      |
+      | (- -4611686018427387904 1)        | -4611686018427387905     |
+
+  @rational
+  Scenario Outline: Use rational numbers
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (= <expression> <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression              | value |
+      | (/ 1 3)                 | 1/3   |
+      | (/ 2 6)                 | 1/3   |
+      | (/ -1 3)                | -1/3  |
+      | (+ 1/3 2/3)             | 1     |
+      | (- 1/2 1/3)             | 1/6   |
+      | (* 2/3 3/4)             | 1/2   |
+      | (/ 1/2 1/4)             | 2     |
+      | (numerator 1/3)         | 1     |
+      | (numerator -2/6)        | -1    |
+      | (denominator 1/3)       | 3     |
+      | (denominator (/ 6 4))   | 2     |
+      | (rationalize 3/10 1/10) | 1/3   |
+      | (rationalize 1/3 0)     | 1/3   |
+      | (floor 7/2)             | 3     |
+      | (floor -7/2)            | -4    |
+      | (round 5/2)             | 2     |
+
+  @rational
+  Scenario Outline: Compare rational numbers
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if <expression> 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression           |
+      | (< 1/3 1/2)          |
+      | (> 1/2 1/3)          |
+      | (= 2/4 1/2)          |
+      | (exact? 1/3)         |
+      | (rational? 1/3)      |
+      | (not (integer? 1/3)) |
+
+  @rational @stak
+  Scenario Outline: Check if a value is an exact rational number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (exact-rational? <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "<output>"
+
+    Examples:
+      | value   | output |
+      | 1/3     | A      |
+      | (/ 1 3) | A      |
+      | 42      | A      |
+      | #f      | B      |
+
+  @rational
+  Scenario Outline: Convert a rational number to a string
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-string (number->string <value>))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "<output>"
+
+    Examples:
+      | value   | output |
+      | 1/3     | 1/3    |
+      | -1/3    | -1/3   |
+      | (/ 4 6) | 2/3    |
+      | (/ 6 3) | 2      |
+
+  @float
+  Scenario Outline: Get a numerator and a denominator of a floating point number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (= <expression> <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression          | value |
+      | (numerator 0.5)     | 1     |
+      | (numerator 0.75)    | 3     |
+      | (numerator -0.75)   | -3    |
+      | (denominator 0.5)   | 2     |
+      | (denominator 0.75)  | 4     |
+      | (denominator -0.75) | 4     |
+
+  @float
+  Scenario Outline: Convert exactness of a number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if (= <expression> <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression           | value |
+      | (exact 2.0)          | 2     |
+      | (exact -3.0)         | -3    |
+      | (inexact 1)          | 1     |
+      | (exact->inexact 1)   | 1     |
+      | (inexact->exact 2.0) | 2     |
+
+  @flonum
+  Scenario Outline: Check exactness of a number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (write-u8 (if <expression> 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression                    |
+      | (exact? 1)                    |
+      | (inexact? 1.5)                |
+      | (inexact? (exact->inexact 1)) |
+      | (exact? (exact 2.0))          |
+      | (exact-integer? (exact 2.0))  |
+      | (not (exact-integer? 2.0))    |
+      | (inexact? (+ 1 0.5))          |
+      | (exact? (+ 1 2))              |
+      | (= (+ 1 0.5) 1.5)             |
+      | (< 1 1.5 2)                   |
+      | (> 2 1.5 1)                   |
+      | (= 2 2.0)                     |
+
   @float
   Scenario: Calculate an exponentiation
     Given a file named "main.scm" with:
@@ -264,6 +494,150 @@ Feature: Number
     When I successfully run `stak main.scm`
     Then the stdout should contain exactly "A"
 
+  @float
+  Scenario Outline: Calculate a trigonometric function
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base) (scheme inexact))
+
+      (write-u8 (if (< (abs (- <expression> <value>)) 0.000001) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression           | value          |
+      | (sin 0)              | 0              |
+      | (cos 0)              | 1              |
+      | (tan 0)              | 0              |
+      | (asin 0)             | 0              |
+      | (acos 1)             | 0              |
+      | (atan 0)             | 0              |
+      | (asin 1)             | (* 2 (atan 1)) |
+      | (acos 0)             | (* 2 (atan 1)) |
+      | (atan 1 1)           | (atan 1)       |
+      | (atan 1 0)           | (* 2 (atan 1)) |
+      | (atan 1 -1)          | (* 3 (atan 1)) |
+      | (sin (* 2 (atan 1))) | 1              |
+      | (cos (* 4 (atan 1))) | -1             |
+
+  Scenario Outline: Calculate a square root of an exact number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base) (scheme inexact))
+
+      (write-u8 (if (= (sqrt <input>) <output>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | input | output |
+      | 0     | 0      |
+      | 1     | 1      |
+      | 4     | 2      |
+      | 9     | 3      |
+      | 144   | 12     |
+
+  @float
+  Scenario: Calculate a square root of an inexact number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base) (scheme inexact))
+
+      (write-u8 (if (< (abs (- (* (sqrt 2) (sqrt 2)) 2)) 0.000001) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+  Scenario Outline: Calculate an exact integer square root
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base))
+
+      (let-values (((root remainder) (exact-integer-sqrt <input>)))
+        (write-u8 (if (and (= root <root>) (= remainder <remainder>)) 65 66)))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | input | root | remainder |
+      | 0     | 0    | 0         |
+      | 1     | 1    | 0         |
+      | 4     | 2    | 0         |
+      | 5     | 2    | 1         |
+      | 17    | 4    | 1         |
+      | 24    | 4    | 8         |
+      | 1000  | 31   | 39        |
+
+  @bignum
+  Scenario Outline: Calculate a square root of a big integer
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base) (scheme inexact))
+
+      (define x (* 100000000000 100000000000))
+
+      (write-u8 (if <expression> 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression                                                                                                 |
+      | (= (sqrt x) 100000000000)                                                                                  |
+      | (exact? (sqrt x))                                                                                          |
+      | (let-values (((root remainder) (exact-integer-sqrt x))) (and (= root 100000000000) (= remainder 0)))       |
+      | (let-values (((root remainder) (exact-integer-sqrt (+ x 1)))) (and (= root 100000000000) (= remainder 1))) |
+      | (finite? x)                                                                                                |
+
+  @rational
+  Scenario Outline: Calculate an inexact function of a rational number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base) (scheme inexact))
+
+      (write-u8 (if <expression> 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "A"
+
+    Examples:
+      | expression               |
+      | (< 1.64 (exp 1/2) 1.65)  |
+      | (< 0.57 (sqrt 1/3) 0.58) |
+      | (finite? 1/3)            |
+      | (not (infinite? 1/3))    |
+      | (not (nan? 1/3))         |
+
+  @float
+  Scenario Outline: Check a class of a floating point number
+    Given a file named "main.scm" with:
+      """scheme
+      (import (scheme base) (scheme inexact))
+
+      (define infinity (/ 1.0 0.0))
+
+      (write-u8 (if (<predicate> <value>) 65 66))
+      """
+    When I successfully run `stak main.scm`
+    Then the stdout should contain exactly "<output>"
+
+    Examples:
+      | predicate | value                 | output |
+      | finite?   | 0                     | A      |
+      | finite?   | 3.14                  | A      |
+      | finite?   | infinity              | B      |
+      | finite?   | (- infinity infinity) | B      |
+      | infinite? | 3.14                  | B      |
+      | infinite? | infinity              | A      |
+      | infinite? | (- infinity)          | A      |
+      | infinite? | (- infinity infinity) | B      |
+      | nan?      | 3.14                  | B      |
+      | nan?      | infinity              | B      |
+      | nan?      | (- infinity infinity) | A      |
+
   @float
   Scenario Outline: Truncate a number
     Given a file named "main.scm" with:
@@ -373,6 +747,8 @@ Feature: Number
       | -1.5  | -2     |
       | -1.51 | -2     |
       | -1.9  | -2     |
+      | -2.5  | -2     |
+      | -3.5  | -4     |
 
   Scenario Outline: Compare numbers
     Given a file named "main.scm" with:
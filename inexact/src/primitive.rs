@@ -2,9 +2,37 @@
 pub(crate) enum Primitive {
     Exponentiation,
     Logarithm,
+    Sine,
+    Cosine,
+    Tangent,
+    Arcsine,
+    Arccosine,
+    Arctangent,
+    SquareRoot,
+    IsFinite,
+    IsInfinite,
+    IsNan,
+    Floor,
+    Ceiling,
+    Round,
+    Truncate,
 }
 
 impl Primitive {
     pub const EXPONENTIATION: usize = Self::Exponentiation as _;
     pub const LOGARITHM: usize = Self::Logarithm as _;
+    pub const SINE: usize = Self::Sine as _;
+    pub const COSINE: usize = Self::Cosine as _;
+    pub const TANGENT: usize = Self::Tangent as _;
+    pub const ARCSINE: usize = Self::Arcsine as _;
+    pub const ARCCOSINE: usize = Self::Arccosine as _;
+    pub const ARCTANGENT: usize = Self::Arctangent as _;
+    pub const SQUARE_ROOT: usize = Self::SquareRoot as _;
+    pub const IS_FINITE: usize = Self::IsFinite as _;
+    pub const IS_INFINITE: usize = Self::IsInfinite as _;
+    pub const IS_NAN: usize = Self::IsNan as _;
+    pub const FLOOR: usize = Self::Floor as _;
+    pub const CEILING: usize = Self::Ceiling as _;
+    pub const ROUND: usize = Self::Round as _;
+    pub const TRUNCATE: usize = Self::Truncate as _;
 }
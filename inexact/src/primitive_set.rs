@@ -1,6 +1,9 @@
 use crate::primitive::Primitive;
-use libm::{exp, log};
-use stak_vm::{Error, Memory, Number, PrimitiveSet};
+use cfg_elif::expr::feature;
+use libm::{acos, asin, atan2, ceil, cos, exp, floor, log, rint, sin, sqrt, tan, trunc};
+#[cfg(not(feature = "flonum"))]
+use stak_vm::Number;
+use stak_vm::{Error, Memory, PrimitiveSet};
 
 /// A primitive set for inexact number operations.
 #[derive(Debug, Default)]
@@ -11,6 +14,47 @@ impl InexactPrimitiveSet {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn operate_unary(memory: &mut Memory, operate: fn(f64) -> f64) -> Result<(), Error> {
+        let x = Self::pop_number(memory)?;
+
+        Self::push_number(memory, operate(x))
+    }
+
+    fn operate_binary(memory: &mut Memory, operate: fn(f64, f64) -> f64) -> Result<(), Error> {
+        let y = Self::pop_number(memory)?;
+        let x = Self::pop_number(memory)?;
+
+        Self::push_number(memory, operate(x, y))
+    }
+
+    fn operate_predicate(memory: &mut Memory, operate: fn(f64) -> bool) -> Result<(), Error> {
+        let x = Self::pop_number(memory)?;
+
+        memory.push(memory.boolean(operate(x)).into())
+    }
+
+    // Numbers on heap other than flonums are not supported.
+    fn pop_number(memory: &mut Memory) -> Result<f64, Error> {
+        let value = memory.pop();
+
+        #[cfg(feature = "flonum")]
+        if let Some(number) = memory.flonum(value) {
+            return Ok(number);
+        }
+
+        Ok(value.to_number().ok_or(Error::NumberExpected)?.to_f64())
+    }
+
+    fn push_number(memory: &mut Memory, number: f64) -> Result<(), Error> {
+        let value = feature!(if ("flonum") {
+            memory.allocate_flonum(number)?.into()
+        } else {
+            Number::from_f64(number).into()
+        });
+
+        memory.push(value)
+    }
 }
 
 impl PrimitiveSet for InexactPrimitiveSet {
@@ -18,10 +62,23 @@ impl PrimitiveSet for InexactPrimitiveSet {
 
     fn operate(&mut self, memory: &mut Memory, primitive: usize) -> Result<(), Self::Error> {
         match primitive {
-            Primitive::EXPONENTIATION => {
-                memory.operate_unary(|x| Number::from_f64(exp(x.to_f64())))?
-            }
-            Primitive::LOGARITHM => memory.operate_unary(|x| Number::from_f64(log(x.to_f64())))?,
+            Primitive::EXPONENTIATION => Self::operate_unary(memory, exp)?,
+            Primitive::LOGARITHM => Self::operate_unary(memory, log)?,
+            Primitive::SINE => Self::operate_unary(memory, sin)?,
+            Primitive::COSINE => Self::operate_unary(memory, cos)?,
+            Primitive::TANGENT => Self::operate_unary(memory, tan)?,
+            Primitive::ARCSINE => Self::operate_unary(memory, asin)?,
+            Primitive::ARCCOSINE => Self::operate_unary(memory, acos)?,
+            Primitive::ARCTANGENT => Self::operate_binary(memory, atan2)?,
+            Primitive::SQUARE_ROOT => Self::operate_unary(memory, sqrt)?,
+            Primitive::IS_FINITE => Self::operate_predicate(memory, f64::is_finite)?,
+            Primitive::IS_INFINITE => Self::operate_predicate(memory, f64::is_infinite)?,
+            Primitive::IS_NAN => Self::operate_predicate(memory, f64::is_nan)?,
+            Primitive::FLOOR => Self::operate_unary(memory, floor)?,
+            Primitive::CEILING => Self::operate_unary(memory, ceil)?,
+            // `rint` rounds halves to even as R7RS requires.
+            Primitive::ROUND => Self::operate_unary(memory, rint)?,
+            Primitive::TRUNCATE => Self::operate_unary(memory, trunc)?,
             _ => return Err(Error::IllegalPrimitive),
         }
 
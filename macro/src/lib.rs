@@ -3,9 +3,9 @@
 use cfg_elif::expr::feature;
 use core::error::Error;
 use proc_macro::TokenStream;
-use proc_macro2::Literal;
+use proc_macro2::{Literal, Span};
 use quote::{quote, ToTokens};
-use stak_compiler::CompileError;
+use stak_compiler::{compile, CompileError, CompileOptions, Location, Prelude};
 use stak_macro_util::{convert_result, read_source_file};
 use std::path::{Path, MAIN_SEPARATOR_STR};
 use syn::{parse::Parse, parse_macro_input, Ident, LitStr, Token};
@@ -66,11 +66,18 @@ fn include_result(input: &IncludeModuleInput) -> Result<proc_macro2::TokenStream
 /// ```rust
 /// const BYTECODE: &[u8] = stak_macro::compile_r7rs!("(define x 42)");
 /// ```
+///
+/// Compile errors point to offending data in a source.
+///
+/// ```rust,compile_fail
+/// const BYTECODE: &[u8] = stak_macro::compile_r7rs!("(define x 42) (define)");
+/// ```
 #[proc_macro]
 pub fn compile_r7rs(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
+    let source = input.value();
 
-    convert_result(generate_r7rs(&input.value())).into()
+    convert_compile_result(generate_r7rs(&source), &input, Some(&source)).into()
 }
 
 /// Includes a module in R7RS Scheme as bytecodes.
@@ -84,13 +91,16 @@ pub fn compile_r7rs(input: TokenStream) -> TokenStream {
 pub fn include_r7rs(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
 
-    convert_result((|| generate_r7rs(&read_source_file(input)?))()).into()
+    convert_compile_result(
+        (|| generate_r7rs(&read_source_file(input.clone())?))(),
+        &input,
+        None,
+    )
+    .into()
 }
 
 fn generate_r7rs(source: &str) -> Result<proc_macro2::TokenStream, Box<dyn Error>> {
-    generate_scheme(source, |source, target| {
-        stak_compiler::compile_r7rs(source, target)
-    })
+    generate_scheme(source, Prelude::R7rs)
 }
 
 /// Compiles a module in Scheme into bytecodes with only built-ins.
@@ -103,8 +113,9 @@ fn generate_r7rs(source: &str) -> Result<proc_macro2::TokenStream, Box<dyn Error
 #[proc_macro]
 pub fn compile_bare(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
+    let source = input.value();
 
-    convert_result(generate_bare(&input.value())).into()
+    convert_compile_result(generate_bare(&source), &input, Some(&source)).into()
 }
 
 /// Includes a module in Scheme as bytecodes with only built-ins.
@@ -118,24 +129,120 @@ pub fn compile_bare(input: TokenStream) -> TokenStream {
 pub fn include_bare(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LitStr);
 
-    convert_result((|| generate_bare(&read_source_file(input)?))()).into()
+    convert_compile_result(
+        (|| generate_bare(&read_source_file(input.clone())?))(),
+        &input,
+        None,
+    )
+    .into()
 }
 
 fn generate_bare(source: &str) -> Result<proc_macro2::TokenStream, Box<dyn Error>> {
-    generate_scheme(source, |source, target| {
-        stak_compiler::compile_bare(source, target)
-    })
+    generate_scheme(source, Prelude::Bare)
 }
 
 fn generate_scheme(
     source: &str,
-    compile: fn(&[u8], &mut Vec<u8>) -> Result<(), CompileError>,
+    prelude: Prelude,
 ) -> Result<proc_macro2::TokenStream, Box<dyn Error>> {
     let mut target = vec![];
 
-    compile(source.as_bytes(), &mut target)?;
+    // Warnings are not reported as macros cannot emit them on stable compilers.
+    compile(
+        source.as_bytes(),
+        &mut target,
+        &CompileOptions::new()
+            .with_prelude(prelude)
+            .with_locations(true),
+    )?;
 
     let target = Literal::byte_string(&target);
 
     Ok(quote! { #target })
 }
+
+// Converts a compile result into a token stream. An error spans an offending
+// datum if a literal is a source itself and the whole literal otherwise.
+fn convert_compile_result(
+    result: Result<proc_macro2::TokenStream, Box<dyn Error>>,
+    literal: &LitStr,
+    source: Option<&str>,
+) -> proc_macro2::TokenStream {
+    result.unwrap_or_else(|error| {
+        let span = error
+            .downcast_ref::<CompileError>()
+            .and_then(CompileError::location)
+            .zip(source)
+            .and_then(|(location, source)| locate(literal, source, location))
+            .unwrap_or_else(|| literal.span());
+
+        syn::Error::new(span, error).to_compile_error()
+    })
+}
+
+// Sub-spans of literals are available only on nightly compilers.
+fn locate(literal: &LitStr, source: &str, location: &Location) -> Option<Span> {
+    let token = literal.token();
+    let string = token.to_string();
+    let prefix = string.find('"')? + 1;
+
+    // Escape sequences shift offsets in a literal.
+    if string.get(prefix..prefix + source.len()) != Some(source) {
+        return None;
+    }
+
+    let start = source_offset(source, location.line(), location.column())?;
+    let end = start
+        + if source[start..].starts_with(location.datum()) {
+            location.datum().len()
+        } else {
+            source[start..].chars().next()?.len_utf8()
+        };
+
+    token.subspan(prefix + start..prefix + end)
+}
+
+// Converts a line and a column in characters starting from 1 into a byte
+// offset in a source.
+fn source_offset(source: &str, line: usize, column: usize) -> Option<usize> {
+    let start = match line.checked_sub(1)? {
+        0 => 0,
+        line => source.match_indices('\n').nth(line - 1)?.0 + 1,
+    };
+
+    source[start..]
+        .char_indices()
+        .take_while(|&(_, character)| character != '\n')
+        .nth(column.checked_sub(1)?)
+        .map(|(offset, _)| start + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_first_line() {
+        assert_eq!(source_offset("(foo)", 1, 1), Some(0));
+        assert_eq!(source_offset("(foo)", 1, 2), Some(1));
+    }
+
+    #[test]
+    fn offset_following_line() {
+        assert_eq!(source_offset("(foo)\n(bar)", 2, 1), Some(6));
+        assert_eq!(source_offset("(foo)\n\n  (bar)", 3, 3), Some(9));
+    }
+
+    #[test]
+    fn offset_multi_byte_characters() {
+        assert_eq!(source_offset("\"あ\" (foo)", 1, 5), Some(6));
+    }
+
+    #[test]
+    fn offset_out_of_range() {
+        assert_eq!(source_offset("(foo)", 0, 1), None);
+        assert_eq!(source_offset("(foo)", 1, 0), None);
+        assert_eq!(source_offset("(foo)", 2, 1), None);
+        assert_eq!(source_offset("(foo)\n(bar)", 1, 7), None);
+    }
+}
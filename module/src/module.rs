@@ -1,6 +1,7 @@
 //! Modules.
 
 use crate::Guard;
+use stak_vm::{Error, Header};
 
 /// A module.
 pub trait Module<'a> {
@@ -9,4 +10,13 @@ pub trait Module<'a> {
 
     /// Returns bytecodes.
     fn bytecode(&'a self) -> Self::Guard;
+
+    /// Returns a header of bytecodes.
+    ///
+    /// It fails if bytecodes are incompatible with virtual machines.
+    fn header(&'a self) -> Result<Header, Error> {
+        let header = Header::decode(&mut self.bytecode().iter().copied())?;
+        header.check(None)?;
+        Ok(header)
+    }
 }
@@ -1,4 +1,4 @@
-use stak_vm::{Error, Memory, PrimitiveSet, Type};
+use stak_vm::{Error, Memory, PrimitiveSet, Type, Value};
 
 /// An equality primitive.
 pub enum EqualPrimitive {
@@ -22,6 +22,45 @@ impl EqualPrimitiveSet {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn eqv(memory: &Memory, x: Value, y: Value) -> bool {
+        x == y
+            || if let (Some(x), Some(y)) = (x.to_cons(), y.to_cons()) {
+                let tag = memory.cdr(x).tag();
+
+                tag == memory.cdr(y).tag()
+                    && (tag == Type::Character as _ && memory.car(x) == memory.car(y)
+                        || (tag == Type::Bignum as _ || tag == Type::Flonum as _)
+                            && memory.car(x) == memory.car(y)
+                            && Self::equal_lists(memory, memory.cdr(x), memory.cdr(y))
+                        || tag == Type::Rational as _
+                            && Self::eqv(memory, memory.car(x), memory.car(y))
+                            && Self::eqv(
+                                memory,
+                                memory.car_value(memory.cdr(x)),
+                                memory.car_value(memory.cdr(y)),
+                            ))
+            } else {
+                false
+            }
+    }
+
+    // Compares lists of numbers, such as limbs of bignums or lower bits of
+    // flonums.
+    fn equal_lists(memory: &Memory, mut x: Value, mut y: Value) -> bool {
+        loop {
+            let (x_cons, y_cons) = (x.assume_cons(), y.assume_cons());
+
+            if x_cons == memory.null() || y_cons == memory.null() {
+                return x_cons == y_cons;
+            } else if memory.car(x_cons) != memory.car(y_cons) {
+                return false;
+            }
+
+            x = memory.cdr(x_cons);
+            y = memory.cdr(y_cons);
+        }
+    }
 }
 
 impl PrimitiveSet for EqualPrimitiveSet {
@@ -32,20 +71,7 @@ impl PrimitiveSet for EqualPrimitiveSet {
             EqualPrimitive::EQV => {
                 let [x, y] = memory.pop_many();
 
-                memory.push(
-                    memory
-                        .boolean(
-                            x == y
-                                || if let (Some(x), Some(y)) = (x.to_cons(), y.to_cons()) {
-                                    memory.cdr(x).tag() == Type::Character as _
-                                        && memory.cdr(y).tag() == Type::Character as _
-                                        && memory.car(x) == memory.car(y)
-                                } else {
-                                    false
-                                },
-                        )
-                        .into(),
-                )?;
+                memory.push(memory.boolean(Self::eqv(memory, x, y)).into())?;
             }
             EqualPrimitive::EQUAL_INNER => {
                 let [x, y] = memory.pop_many();
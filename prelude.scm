@@ -83,6 +83,8 @@
     number?
     exact?
     inexact?
+    exact-integer?
+    exact-rational?
     zero?
     positive?
     negative?
@@ -98,16 +100,36 @@
     truncate-quotient
     modulo
     floor-remainder
+    floor-quotient
+    floor/
+    truncate/
+    square
     truncate
     floor
     ceiling
     round
+    numerator
+    denominator
+    rationalize
     exact
     inexact
+    exact->inexact
+    inexact->exact
     abs
     exp
     expt
     log
+    sin
+    cos
+    tan
+    asin
+    acos
+    atan
+    sqrt
+    exact-integer-sqrt
+    finite?
+    infinite?
+    nan?
     =
     <
     >
@@ -574,6 +596,10 @@
     (define vector-type 7)
     (define bytevector-type 8)
     (define record-type 9)
+    (define foreign-type 10)
+    (define bignum-type 11)
+    (define rational-type 12)
+    (define flonum-type 13)
 
     ; Primitives
 
@@ -597,6 +623,26 @@
     (define remainder (primitive 14))
     (define exp (primitive 15))
     (define $log (primitive 16))
+    (define sin (primitive 17))
+    (define cos (primitive 18))
+    (define tan (primitive 19))
+    (define asin (primitive 20))
+    (define acos (primitive 21))
+    (define $atan (primitive 22))
+    (define $sqrt (primitive 23))
+    (define finite? (primitive 24))
+    (define infinite? (primitive 25))
+    (define nan? (primitive 26))
+    (define $floor (primitive 27))
+    (define $ceiling (primitive 28))
+    (define $round (primitive 29))
+    (define $truncate (primitive 30))
+    (define floor-quotient (primitive 31))
+    (define $floor/ (primitive 32))
+    (define $truncate/ (primitive 33))
+    (define $= (primitive 35))
+    (define $exact (primitive 36))
+    (define $inexact (primitive 37))
     (define null? (primitive 50))
     (define pair? (primitive 51))
     (define assq (primitive 60))
@@ -650,8 +696,12 @@
 
     ;; Number
 
+    (define bignum? (instance? bignum-type))
+    (define ratnum? (instance? rational-type))
+    (define flonum? (instance? flonum-type))
+
     (define (number? x)
-      (not (rib? x)))
+      (or (not (rib? x)) (bignum? x) (ratnum? x) (flonum? x)))
 
     (define complex? number?)
     (define real? complex?)
@@ -661,11 +711,24 @@
         (number? x)
         (zero? (remainder x 1))))
 
-    (define exact? integer?)
+    (define (exact? x)
+      (cond
+        ((flonum? x)
+          #f)
+
+        ((ratnum? x)
+          #t)
+
+        (else
+          (integer? x))))
     (define (inexact? x)
       (not (exact? x)))
+    (define (exact-integer? x)
+      (and (exact? x) (integer? x)))
+    (define (exact-rational? x)
+      (and (rational? x) (exact? x)))
 
-    (define (zero? x) (eq? x 0))
+    (define (zero? x) ($= x 0))
     (define (positive? x) (> x 0))
     (define (negative? x) (< x 0))
     (define (even? x) (zero? (modulo x 2)))
@@ -674,7 +737,7 @@
     (define-optimizer zero?
       (syntax-rules ()
         ((_ x)
-          (eq? x 0))))
+          ($= x 0))))
 
     (define (arithmetic-operator f y)
       (lambda xs (fold-left f y xs)))
@@ -724,28 +787,113 @@
 
     (define floor-remainder modulo)
 
-    (define (truncate x)
-      (quotient x 1))
+    (define (division-operator f)
+      (lambda (x y)
+        (let ((pair (f x y)))
+          (values (rib-car pair) (rib-cdr pair)))))
 
-    (define (floor x)
-      (let ((y (quotient x 1)))
-        (if (negative? (remainder x 1))
-          (- y 1)
-          y)))
+    (define floor/ (division-operator $floor/))
+    (define truncate/ (division-operator $truncate/))
 
-    (define (ceiling x)
-      (- (floor (- x))))
+    (define (square x)
+      (* x x))
 
-    (define (round x)
-      (let* ((x (* x 2))
-             (y (floor (/ (+ x 1) 2))))
-        (if (= (modulo x 2) 1)
-          (- y (modulo y 2))
-          y)))
+    (define (rounding-operator exact-operator inexact-operator)
+      (lambda (x)
+        ((if (exact? x) exact-operator inexact-operator) x)))
+
+    (define truncate
+      (rounding-operator
+        (lambda (x) (quotient x 1))
+        $truncate))
+
+    (define floor
+      (rounding-operator
+        (lambda (x)
+          (let ((y (quotient x 1)))
+            (if (negative? (remainder x 1))
+              (- y 1)
+              y)))
+        $floor))
+
+    (define ceiling
+      (rounding-operator
+        (lambda (x) (- (floor (- x))))
+        $ceiling))
+
+    (define round
+      (rounding-operator
+        (lambda (x)
+          (let* ((x (* x 2))
+                 (y (floor (/ (+ x 1) 2))))
+            (if (= (modulo x 2) 1)
+              (- y (modulo y 2))
+              y)))
+        $round))
+
+    (define (denominator x)
+      (cond
+        ((ratnum? x)
+          (rib-car (rib-cdr x)))
+
+        ((exact? x)
+          1)
+
+        (else
+          (let loop ((x x) (y 1))
+            (if (integer? x)
+              y
+              (loop (* x 2) (* y 2)))))))
+
+    (define (numerator x)
+      (if (ratnum? x)
+        (rib-car x)
+        (* x (denominator x))))
+
+    (define (rationalize x y)
+      (define (simplest x y)
+        (let ((z (floor x)))
+          (cond
+            ((= z x)
+              z)
+
+            ((< z (floor y))
+              (+ z 1))
+
+            (else
+              (+ z (/ 1 (simplest (/ 1 (- y z)) (/ 1 (- x z)))))))))
+
+      (let ((x (- x (abs y)))
+            (y (+ x (abs y))))
+        (cond
+          ((positive? x)
+            (simplest x y))
+
+          ((negative? y)
+            (- (simplest (- y) (- x))))
+
+          (else
+            (* x 0)))))
+
+    (define (exact x)
+      (cond
+        ((exact? x)
+          x)
+
+        ((flonum? x)
+          (let ((y ($exact x)))
+            (if (= y x)
+              y
+              ($exact (round x)))))
+
+        (else
+          (round x))))
 
-    (define exact round)
     (define (inexact x)
-      x)
+      ($inexact x))
+
+    (define exact->inexact inexact)
+    (define inexact->exact exact)
 
     (define (abs x)
       (if (negative? x)
@@ -760,6 +908,28 @@
     (define (expt x y)
       (exp (* (log x) y)))
 
+    (define (atan y . xs)
+      ($atan y (if (null? xs) 1 (car xs))))
+
+    (define (sqrt x)
+      (let ((y ($sqrt x)))
+        (if (and (exact? x) (not (negative? x)))
+          (let ((z (exact (round y))))
+            (if (= (* z z) x) z y))
+          y)))
+
+    (define (exact-integer-sqrt x)
+      (let loop ((y (exact (floor ($sqrt x)))))
+        (cond
+          ((< x (* y y))
+            (loop (- y 1)))
+
+          ((<= (* (+ y 1) (+ y 1)) x)
+            (loop (+ y 1)))
+
+          (else
+            (values y (- x (* y y)))))))
+
     (define (comparison-operator f)
       (lambda xs
         (boolean-or
@@ -771,7 +941,7 @@
               (let ((y (car xs)))
                 (and (f x y) (loop y (cdr xs)))))))))
 
-    (define = (comparison-operator eq?))
+    (define = (comparison-operator $=))
     (define < (comparison-operator $<))
     (define > (comparison-operator (lambda (x y) ($< y x))))
     (define <= (comparison-operator (lambda (x y) (not ($< y x)))))
@@ -780,7 +950,7 @@
     (define-optimizer =
       (syntax-rules ()
         ((_ x y)
-          (eq? x y))))
+          ($= x y))))
 
     (define-optimizer <
       (syntax-rules ()
@@ -1105,10 +1275,11 @@
       (define radix (if (null? rest) 10 (car rest)))
 
       (define (format-digit x)
-        (integer->char
-          (if (< 9 x)
-            (+ (char->integer #\a) (- x 10))
-            (+ (char->integer #\0) x))))
+        (let ((x (exact x)))
+          (integer->char
+            (if (< 9 x)
+              (+ (char->integer #\a) (- x 10))
+              (+ (char->integer #\0) x)))))
 
       (define (format-point x)
         (if (< x epsilon)
@@ -1130,21 +1301,28 @@
                       (format-digit q)
                       (loop r d ys)))))))))
 
-      (list->string
-        (append
-          (if (negative? x)
-            (list #\-)
-            '())
-          (let loop ((x (abs x)) (ys '()))
-            (let* ((q (quotient x radix))
-                   (ys
-                     (cons
-                       (format-digit (quotient (remainder x radix) 1))
-                       ys)))
-              (if (positive? q)
-                (loop q ys)
-                ys)))
-          (format-point (remainder (abs x) 1)))))
+      (if (ratnum? x)
+        (string-append
+          (number->string (numerator x) radix)
+          "/"
+          (number->string (denominator x) radix))
+        (list->string
+          (append
+            (if (negative? x)
+              (list #\-)
+              '())
+            (let loop ((x (abs x)) (ys '()))
+              (let* ((q (quotient x radix))
+                     (ys
+                       (cons
+                         (format-digit (quotient (remainder x radix) 1))
+                         ys)))
+                (if (positive? q)
+                  (loop q ys)
+                  ys)))
+            (if (and (flonum? x) (integer? x))
+              (list #\. #\0)
+              (format-point (remainder (abs x) 1)))))))
 
     (define (string->number x . rest)
       (define radix (if (null? rest) 10 (car rest)))
@@ -1178,7 +1356,7 @@
       (define (convert-point xs)
         (let loop ((xs xs) (y 0) (d 1))
           (if (null? xs)
-            (/ y d)
+            (inexact (/ y d))
             (let ((x (convert-digit (car xs))))
               (and
                 x
@@ -1200,6 +1378,12 @@
                   (eqv? (car xs) #\.))
                 (+ y (convert-point (cdr xs))))
 
+              ((and
+                  (not initial)
+                  (eqv? (car xs) #\/))
+                (let ((x (convert (cdr xs))))
+                  (and x (/ y x))))
+
               (else
                 (let ((x (convert-digit (car xs))))
                   (and x (loop #f (cdr xs) (+ (* radix y) x)))))))))
@@ -1443,6 +1627,8 @@
     number?
     exact?
     inexact?
+    exact-integer?
+    exact-rational?
     zero?
     positive?
     negative?
@@ -1458,14 +1644,24 @@
     truncate-quotient
     modulo
     floor-remainder
+    floor-quotient
+    floor/
+    truncate/
+    square
     truncate
     floor
     ceiling
     round
+    numerator
+    denominator
+    rationalize
     exact
     inexact
+    exact->inexact
+    inexact->exact
     abs
     expt
+    exact-integer-sqrt
     =
     <
     >
@@ -1632,17 +1828,19 @@
 
     ; Symbol table
 
-    (define string->symbol
-      (let ((symbols ($$symbols)))
-        (lambda (x)
-          (cond
-            ((member x symbols (lambda (x y) (equal? x (symbol->string y)))) =>
-              car)
+    ; We keep a symbol table in a `car` of `#t` so that primitives can intern
+    ; symbols as well.
+    (rib-set-car! #t ($$symbols))
 
-            (else
-              (let ((x (string->uninterned-symbol x)))
-                (set! symbols (cons x symbols))
-                x))))))
+    (define (string->symbol x)
+      (cond
+        ((member x (rib-car #t) (lambda (x y) (equal? x (symbol->string y)))) =>
+          car)
+
+        (else
+          (let ((x (string->uninterned-symbol x)))
+            (rib-set-car! #t (cons x (rib-car #t)))
+            x))))
 
     ; Control
 
@@ -1980,9 +2178,22 @@
       (write-string "<unknown>" (get-output-port rest)))))
 
 (define-library (scheme inexact)
-  (export exp log)
+  (export
+    exp
+    log
+    sin
+    cos
+    tan
+    asin
+    acos
+    atan
+    sqrt
+    finite?
+    infinite?
+    nan?)
 
-  (import (only (stak base) exp log)))
+  (import
+    (only (stak base) exp log sin cos tan asin acos atan sqrt finite? infinite? nan?)))
 
 (define-library (scheme cxr)
   (export
@@ -3365,15 +3576,16 @@
   (export define-rust)
 
   (import
-    (only (stak base) primitive)
+    (only (stak base) procedure-type rib)
     (scheme base))
 
   (begin
+    ; Rust functions raise errors with procedures in their environments.
     (define-syntax define-rust
       (syntax-rules ()
         ((_ "count" index name1 name2 ...)
           (begin
-            (define name1 (primitive index))
+            (define name1 (rib index error procedure-type))
             (define-rust "count" (+ index 1) name2 ...)))
 
         ((_ "count" index)
@@ -1,5 +1,5 @@
 use crate::{ProcedureOperation, COLUMN_SEPARATOR, FRAME_SEPARATOR};
-use stak_vm::{Cons, Memory, Profiler, StackSlot};
+use stak_vm::{Cons, Frame, Memory, Profiler, StackSlot};
 use std::{io::Write, time::Instant};
 
 /// A stack profiler.
@@ -50,6 +50,10 @@ impl<T: Write> StackProfiler<T> {
                 string = memory.cdr(string).assume_cons();
             }
         }
+
+        if let Some(position) = Frame::new(memory, code).position() {
+            write!(self.writer, "@{position}").unwrap();
+        }
     }
 
     fn write_stack(&mut self, memory: &Memory) {
@@ -102,6 +106,19 @@ impl<T: Write> Profiler for StackProfiler<T> {
         self.write_time();
     }
 
+    fn profile_garbage_collection(&mut self, memory: &Memory) {
+        write!(self.writer, "garbage_collection").unwrap();
+        self.write_column_separator();
+        write!(
+            self.writer,
+            "{}",
+            memory.garbage_collection_statistics().live_cons_count()
+        )
+        .unwrap();
+        self.write_column_separator();
+        self.write_time();
+    }
+
     fn profile_event(&mut self, name: &str) {
         write!(self.writer, "{name}").unwrap();
         self.write_column_separator();
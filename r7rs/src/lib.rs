@@ -5,6 +5,9 @@
 
 #![no_std]
 
+#[cfg(feature = "bignum")]
+extern crate alloc;
+
 mod small;
 
 pub use small::{Error as SmallError, SmallPrimitiveSet};
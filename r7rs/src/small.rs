@@ -1,8 +1,21 @@
+#[cfg(feature = "bignum")]
+mod bignum;
 mod error;
+#[cfg(feature = "flonum")]
+mod flonum;
 mod primitive;
+#[cfg(feature = "rational")]
+mod rational;
 
+#[cfg(all(feature = "bignum", not(feature = "rational")))]
+use self::bignum::Bignum as Exact;
 pub use self::error::Error;
+#[cfg(feature = "flonum")]
+use self::flonum::Real;
 use self::primitive::Primitive;
+#[cfg(feature = "rational")]
+use self::rational::Rational as Exact;
+#[cfg(not(feature = "bignum"))]
 use core::ops::{Add, Div, Mul, Rem, Sub};
 use stak_device::{Device, DevicePrimitiveSet};
 use stak_file::{FilePrimitiveSet, FileSystem};
@@ -10,7 +23,12 @@ use stak_inexact::InexactPrimitiveSet;
 use stak_native::{EqualPrimitiveSet, ListPrimitiveSet, TypeCheckPrimitiveSet};
 use stak_process_context::{ProcessContext, ProcessContextPrimitiveSet};
 use stak_time::{Clock, TimePrimitiveSet};
-use stak_vm::{Memory, Number, NumberRepresentation, PrimitiveSet, Tag, Type, Value};
+#[cfg(not(feature = "bignum"))]
+use stak_vm::NumberRepresentation;
+use stak_vm::{Memory, Number, PrimitiveSet, Tag, Type, Value};
+
+#[cfg(all(feature = "bignum", not(feature = "flonum")))]
+type Real = Exact;
 
 /// A primitive set that covers [the R7RS small](https://standards.scheme.org/corrected-r7rs/r7rs.html).
 pub struct SmallPrimitiveSet<D: Device, F: FileSystem, P: ProcessContext, C: Clock> {
@@ -49,6 +67,7 @@ impl<D: Device, F: FileSystem, P: ProcessContext, C: Clock> SmallPrimitiveSet<D,
         self.device.device_mut()
     }
 
+    #[cfg(not(feature = "bignum"))]
     fn operate_comparison(
         memory: &mut Memory,
         operate: fn(NumberRepresentation, NumberRepresentation) -> bool,
@@ -63,6 +82,186 @@ impl<D: Device, F: FileSystem, P: ProcessContext, C: Clock> SmallPrimitiveSet<D,
         Ok(())
     }
 
+    #[cfg(feature = "bignum")]
+    fn operate_real_comparison(
+        memory: &mut Memory,
+        operate_fixnum: fn(Number, Number) -> bool,
+        operate: fn(&Real, &Real) -> bool,
+    ) -> Result<(), Error> {
+        let [x, y] = memory.pop_many();
+
+        let value = if let (Some(x), Some(y)) = (x.to_number(), y.to_number()) {
+            operate_fixnum(x, y)
+        } else {
+            operate(&Real::read(memory, x)?, &Real::read(memory, y)?)
+        };
+
+        memory.push(memory.boolean(value).into())?;
+        Ok(())
+    }
+
+    // Operates on fixnums and promotes them to bignums, rational numbers, or
+    // flonums if results are out of fixnums. An operation on the other numbers
+    // returns `None` on division by zero.
+    #[cfg(feature = "bignum")]
+    fn operate_real(
+        memory: &mut Memory,
+        operate_fixnum: fn(Number, Number) -> Option<Number>,
+        operate: fn(&Real, &Real) -> Option<Real>,
+    ) -> Result<(), Error> {
+        let [x, y] = memory.pop_many();
+
+        let value = if let Some(z) = x
+            .to_number()
+            .zip(y.to_number())
+            .and_then(|(x, y)| operate_fixnum(x, y))
+        {
+            z.into()
+        } else {
+            operate(&Real::read(memory, x)?, &Real::read(memory, y)?)
+                .ok_or(Error::DivisionByZero)?
+                .write(memory)?
+        };
+
+        memory.push(value)?;
+        Ok(())
+    }
+
+    // Fixnums of floating-point numbers can have fractions. We divide them as
+    // they are.
+    #[cfg(feature = "bignum")]
+    fn divide_fixnums(x: Number, y: Number) -> Option<Number> {
+        if cfg!(any(feature = "rational", feature = "flonum"))
+            && bignum::Bignum::is_integer(x)
+            && bignum::Bignum::is_integer(y)
+            && x.checked_rem(y)? != Number::default()
+        {
+            return None;
+        }
+
+        x.checked_div(y)
+    }
+
+    // Divides numbers into a quotient and a remainder. A quotient is rounded
+    // towards negative infinity on `floor` or zero otherwise.
+    fn divide_integer_fixnums(x: Number, y: Number, floor: bool) -> Option<(Number, Number)> {
+        let zero = Number::default();
+        let mut remainder = x.checked_rem(y)?;
+
+        if floor && remainder != zero && (remainder < zero) != (y < zero) {
+            remainder = remainder.checked_add(y)?;
+        }
+
+        Some((x.checked_sub(remainder)?.checked_div(y)?, remainder))
+    }
+
+    #[cfg(feature = "bignum")]
+    fn divide_integer_reals(x: &Real, y: &Real, zero: &Real, floor: bool) -> Option<(Real, Real)> {
+        let mut remainder = x.checked_rem(y)?;
+
+        if floor && remainder != *zero && (remainder < *zero) != (*y < *zero) {
+            remainder = remainder.checked_add(y)?;
+        }
+
+        Some((x.checked_sub(&remainder)?.checked_div(y)?, remainder))
+    }
+
+    // Pushes a quotient, or a pair of a quotient and a remainder on `pair`.
+    fn operate_integer_division(memory: &mut Memory, floor: bool, pair: bool) -> Result<(), Error> {
+        let [x, y] = memory.pop_many();
+
+        let (quotient, remainder) = if let Some((quotient, remainder)) = x
+            .to_number()
+            .zip(y.to_number())
+            .and_then(|(x, y)| Self::divide_integer_fixnums(x, y, floor))
+        {
+            (quotient.into(), remainder.into())
+        } else {
+            #[cfg(feature = "bignum")]
+            {
+                let zero = Real::read(memory, Number::default().into())?;
+                let (quotient, remainder) = Self::divide_integer_reals(
+                    &Real::read(memory, x)?,
+                    &Real::read(memory, y)?,
+                    &zero,
+                    floor,
+                )
+                .ok_or(Error::DivisionByZero)?;
+
+                // Keep a remainder on a stack as garbage collection on
+                // allocation of a quotient can move it.
+                let remainder = if pair {
+                    remainder.write(memory)?
+                } else {
+                    Number::default().into()
+                };
+                memory.push(remainder)?;
+                let quotient = quotient.write(memory)?;
+
+                (quotient, memory.pop())
+            }
+            #[cfg(not(feature = "bignum"))]
+            return Err(if !x.is_number() || !y.is_number() {
+                stak_vm::Error::NumberExpected.into()
+            } else if y.to_number() == Some(Number::default()) {
+                Error::DivisionByZero
+            } else {
+                Error::NumberOverflow
+            });
+        };
+
+        if pair {
+            Self::rib(memory, quotient, remainder, Type::Pair as _)
+        } else {
+            memory.push(quotient)?;
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "flonum")]
+    fn convert_exactness(
+        memory: &mut Memory,
+        convert: fn(&Real) -> Option<Real>,
+    ) -> Result<(), Error> {
+        let value = memory.pop();
+        let value = convert(&Real::read(memory, value)?)
+            .ok_or(Error::NonFiniteNumber)?
+            .write(memory)?;
+
+        memory.push(value)?;
+        Ok(())
+    }
+
+    // Converts exact numbers on heap into inexact ones in place as inexact
+    // primitives know only fixnums and flonums.
+    #[cfg(feature = "bignum")]
+    fn convert_inexact_arguments(memory: &mut Memory, count: usize) -> Result<(), Error> {
+        for index in 0..count {
+            let value = memory.car(memory.tail(memory.stack(), index));
+
+            #[cfg(feature = "flonum")]
+            if memory.flonum(value).is_some() {
+                continue;
+            }
+
+            if value.is_number() {
+                continue;
+            }
+
+            let number = Real::read(memory, value)?.to_f64();
+            #[cfg(feature = "flonum")]
+            let value = memory.allocate_flonum(number)?.into();
+            #[cfg(not(feature = "flonum"))]
+            let value = Number::from_f64(number).into();
+
+            // Allocation can move a stack.
+            let cons = memory.tail(memory.stack(), index);
+            memory.set_car(cons, value);
+        }
+
+        Ok(())
+    }
+
     fn rib(memory: &mut Memory, car: Value, cdr: Value, tag: Tag) -> Result<(), Error> {
         let rib = memory.allocate(car, cdr.set_tag(tag))?;
         memory.push(rib.into())?;
@@ -130,15 +329,87 @@ impl<D: Device, F: FileSystem, P: ProcessContext, C: Clock> PrimitiveSet
                 let [x, y] = memory.pop_many();
                 memory.push(memory.boolean(x == y).into())?;
             }
+            #[cfg(not(feature = "bignum"))]
             Primitive::LESS_THAN => Self::operate_comparison(memory, |x, y| x < y)?,
+            #[cfg(not(feature = "bignum"))]
             Primitive::ADD => memory.operate_binary(Add::add)?,
+            #[cfg(not(feature = "bignum"))]
             Primitive::SUBTRACT => memory.operate_binary(Sub::sub)?,
+            #[cfg(not(feature = "bignum"))]
             Primitive::MULTIPLY => memory.operate_binary(Mul::mul)?,
+            #[cfg(not(feature = "bignum"))]
             Primitive::DIVIDE => memory.operate_binary(Div::div)?,
+            #[cfg(not(feature = "bignum"))]
             Primitive::REMAINDER => memory.operate_binary(Rem::rem)?,
-            Primitive::EXPONENTIATION | Primitive::LOGARITHM => self
-                .inexact
-                .operate(memory, primitive - Primitive::EXPONENTIATION)?,
+            #[cfg(feature = "bignum")]
+            Primitive::LESS_THAN => {
+                Self::operate_real_comparison(memory, |x, y| x < y, |x, y| x < y)?
+            }
+            #[cfg(feature = "bignum")]
+            Primitive::ADD => Self::operate_real(memory, Number::checked_add, Real::checked_add)?,
+            #[cfg(feature = "bignum")]
+            Primitive::SUBTRACT => {
+                Self::operate_real(memory, Number::checked_sub, Real::checked_sub)?
+            }
+            #[cfg(feature = "bignum")]
+            Primitive::MULTIPLY => {
+                Self::operate_real(memory, Number::checked_mul, Real::checked_mul)?
+            }
+            #[cfg(feature = "bignum")]
+            Primitive::DIVIDE => {
+                Self::operate_real(memory, Self::divide_fixnums, Real::checked_div)?
+            }
+            #[cfg(feature = "bignum")]
+            Primitive::REMAINDER => {
+                Self::operate_real(memory, Number::checked_rem, Real::checked_rem)?
+            }
+            #[cfg(not(feature = "bignum"))]
+            Primitive::NUMBER_EQUAL => Self::operate_comparison(memory, |x, y| x == y)?,
+            #[cfg(feature = "bignum")]
+            Primitive::NUMBER_EQUAL => {
+                Self::operate_real_comparison(memory, |x, y| x == y, |x, y| x == y)?
+            }
+            #[cfg(feature = "flonum")]
+            Primitive::EXACT => Self::convert_exactness(memory, Real::to_exact)?,
+            #[cfg(feature = "flonum")]
+            Primitive::INEXACT => {
+                Self::convert_exactness(memory, |number| Some(number.to_inexact()))?
+            }
+            // Without flonums, all numbers are in a single exactness.
+            #[cfg(not(feature = "flonum"))]
+            Primitive::EXACT | Primitive::INEXACT => {}
+            Primitive::EXPONENTIATION
+            | Primitive::LOGARITHM
+            | Primitive::SINE
+            | Primitive::COSINE
+            | Primitive::TANGENT
+            | Primitive::ARCSINE
+            | Primitive::ARCCOSINE
+            | Primitive::ARCTANGENT
+            | Primitive::SQUARE_ROOT
+            | Primitive::IS_FINITE
+            | Primitive::IS_INFINITE
+            | Primitive::IS_NAN
+            | Primitive::FLOOR
+            | Primitive::CEILING
+            | Primitive::ROUND
+            | Primitive::TRUNCATE => {
+                #[cfg(feature = "bignum")]
+                Self::convert_inexact_arguments(
+                    memory,
+                    if primitive == Primitive::ARCTANGENT {
+                        2
+                    } else {
+                        1
+                    },
+                )?;
+
+                self.inexact
+                    .operate(memory, primitive - Primitive::EXPONENTIATION)?
+            }
+            Primitive::FLOOR_QUOTIENT => Self::operate_integer_division(memory, true, false)?,
+            Primitive::FLOOR_DIVIDE => Self::operate_integer_division(memory, true, true)?,
+            Primitive::TRUNCATE_DIVIDE => Self::operate_integer_division(memory, false, true)?,
             Primitive::HALT => return Err(Error::Halt),
             Primitive::NULL | Primitive::PAIR => self
                 .type_check
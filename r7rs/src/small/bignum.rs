@@ -0,0 +1,620 @@
+use alloc::{vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+use stak_vm::{Error, Memory, Number, Type, Value};
+
+const LIMB_BITS: usize = u32::BITS as _;
+
+// An arbitrary-precision integer.
+//
+// On heap, a bignum is a rib of a sign and a list of 32-bit limbs in a
+// little-endian order with a tag of `Type::Bignum`. Integers in a range of
+// fixnums are never represented as bignums.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bignum {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl Bignum {
+    pub fn from_i64(number: i64) -> Self {
+        let mut magnitude = number.unsigned_abs();
+        let mut limbs = vec![];
+
+        while magnitude > 0 {
+            limbs.push(magnitude as _);
+            magnitude >>= LIMB_BITS;
+        }
+
+        Self::new(number < 0, limbs)
+    }
+
+    // Reads a fixnum or a bignum on heap.
+    pub fn read(memory: &Memory, value: Value) -> Result<Self, Error> {
+        let Some(cons) = value.to_cons() else {
+            return Self::from_number(value.assume_number()).ok_or(Error::NumberExpected);
+        };
+
+        if memory.cdr(cons).tag() != Type::Bignum as _ {
+            return Err(Error::NumberExpected);
+        }
+
+        let mut limbs = vec![];
+        let mut list = memory.cdr(cons).assume_cons();
+
+        while list != memory.null() {
+            limbs.push(memory.car(list).assume_number().to_i64() as _);
+            list = memory.cdr(list).assume_cons();
+        }
+
+        Ok(Self::new(
+            memory.car(cons).assume_number().to_i64() < 0,
+            limbs,
+        ))
+    }
+
+    // Returns `true` if a fixnum is an integer. A fixnum of a floating-point
+    // number can have a fraction.
+    pub fn is_integer(number: Number) -> bool {
+        Self::to_small_integer(number).is_some() || Self::from_number(number).is_some()
+    }
+
+    // Converts a fixnum into an integer. A fixnum of a floating-point number
+    // must have no fraction.
+    pub fn from_number(number: Number) -> Option<Self> {
+        if let Some(integer) = Self::to_small_integer(number) {
+            return Some(Self::from_i64(integer));
+        }
+
+        let (mantissa, exponent) = Self::decompose_f64(number.to_f64())?;
+
+        (exponent >= 0 || mantissa.trailing_zeros() as isize >= -exponent)
+            .then(|| Self::from_f64(number.to_f64()))
+            .flatten()
+    }
+
+    fn to_small_integer(number: Number) -> Option<i64> {
+        let integer = number.to_i64();
+
+        // A conversion into `i64` saturates on overflow.
+        (Number::from_i64(integer) == number && integer != i64::MAX && integer != i64::MIN)
+            .then_some(integer)
+    }
+
+    // Writes an integer as a fixnum or a bignum on heap.
+    pub fn write(&self, memory: &mut Memory) -> Result<Value, Error> {
+        if let Some(number) = self.to_number() {
+            return Ok(number.into());
+        }
+
+        let mut list = memory.null();
+
+        for &limb in self.limbs.iter().rev() {
+            list = memory.cons(Number::from_i64(limb as _).into(), list)?;
+        }
+
+        Ok(memory
+            .allocate(
+                Number::from_i64(if self.negative { -1 } else { 1 }).into(),
+                list.set_tag(Type::Bignum as _).into(),
+            )?
+            .into())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        let (quotient, _) = Self::divide_magnitudes(&self.limbs, &other.limbs)?;
+
+        Some(Self::new(self.negative != other.negative, quotient))
+    }
+
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        let (_, remainder) = Self::divide_magnitudes(&self.limbs, &other.limbs)?;
+
+        Some(Self::new(self.negative, remainder))
+    }
+
+    // Converts a finite floating-point number into an integer truncating its
+    // fraction.
+    pub fn from_f64(number: f64) -> Option<Self> {
+        let (mantissa, exponent) = Self::decompose_f64(number)?;
+
+        Some(if exponent < 0 {
+            let magnitude = mantissa
+                .unsigned_abs()
+                .checked_shr(exponent.unsigned_abs() as _)
+                .unwrap_or_default();
+
+            Self::new(
+                mantissa < 0,
+                vec![magnitude as _, (magnitude >> LIMB_BITS) as _],
+            )
+        } else {
+            &Self::from_i64(mantissa) * &Self::power_of_two(exponent as _)
+        })
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.limbs.iter().rev().fold(0.0, |magnitude, &limb| {
+            magnitude * (1u64 << LIMB_BITS) as f64 + limb as f64
+        });
+
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    // Decomposes a finite floating-point number into a mantissa and an
+    // exponent of two.
+    pub fn decompose_f64(number: f64) -> Option<(i64, isize)> {
+        if !number.is_finite() {
+            return None;
+        }
+
+        let bits = number.to_bits();
+        let exponent = (bits >> 52 & 0x7ff) as isize;
+        let mantissa = (bits & ((1 << 52) - 1)) as i64;
+        let (mantissa, exponent) = if exponent == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | 1 << 52, exponent - 1075)
+        };
+
+        Some((
+            if number.is_sign_negative() {
+                -mantissa
+            } else {
+                mantissa
+            },
+            exponent,
+        ))
+    }
+
+    pub fn power_of_two(exponent: usize) -> Self {
+        let mut limbs = vec![0; exponent / LIMB_BITS + 1];
+        limbs[exponent / LIMB_BITS] = 1 << (exponent % LIMB_BITS);
+
+        Self::new(false, limbs)
+    }
+
+    fn new(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        Self {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    fn to_number(&self) -> Option<Number> {
+        if self.limbs.len() > 2 {
+            return None;
+        }
+
+        let magnitude = self.limbs.iter().rev().fold(0u64, |magnitude, &limb| {
+            magnitude << LIMB_BITS | limb as u64
+        });
+        let integer = if self.negative {
+            0i64.checked_sub_unsigned(magnitude)?
+        } else {
+            i64::try_from(magnitude).ok()?
+        };
+        let number = Number::from_i64(integer);
+
+        (number.to_i64() == integer).then_some(number)
+    }
+
+    fn compare_magnitudes(x: &[u32], y: &[u32]) -> Ordering {
+        x.len()
+            .cmp(&y.len())
+            .then_with(|| x.iter().rev().cmp(y.iter().rev()))
+    }
+
+    fn add_magnitudes(x: &[u32], y: &[u32]) -> Vec<u32> {
+        let mut limbs = Vec::with_capacity(x.len().max(y.len()) + 1);
+        let mut carry = 0;
+
+        for index in 0..x.len().max(y.len()) {
+            let sum = Self::limb(x, index) + Self::limb(y, index) + carry;
+
+            limbs.push(sum as _);
+            carry = sum >> LIMB_BITS;
+        }
+
+        limbs.push(carry as _);
+        limbs
+    }
+
+    // Subtracts a smaller magnitude from a larger one.
+    fn subtract_magnitudes(x: &[u32], y: &[u32]) -> Vec<u32> {
+        let mut limbs = Vec::with_capacity(x.len());
+        let mut borrow = 0;
+
+        for (index, &limb) in x.iter().enumerate() {
+            let (difference, overflow) =
+                (limb as u64).overflowing_sub(Self::limb(y, index) + borrow);
+
+            limbs.push(difference as _);
+            borrow = overflow as _;
+        }
+
+        limbs
+    }
+
+    fn multiply_magnitudes(x: &[u32], y: &[u32]) -> Vec<u32> {
+        let mut limbs = vec![0; x.len() + y.len()];
+
+        for (i, &x) in x.iter().enumerate() {
+            let mut carry = 0;
+
+            for (j, &y) in y.iter().enumerate() {
+                let product = x as u64 * y as u64 + limbs[i + j] as u64 + carry;
+
+                limbs[i + j] = product as _;
+                carry = product >> LIMB_BITS;
+            }
+
+            limbs[i + y.len()] = carry as _;
+        }
+
+        limbs
+    }
+
+    // Divides magnitudes by bitwise long division. It returns `None` on
+    // division by zero.
+    fn divide_magnitudes(x: &[u32], y: &[u32]) -> Option<(Vec<u32>, Vec<u32>)> {
+        if y.is_empty() {
+            return None;
+        } else if let [y] = y {
+            return Some(Self::divide_magnitude_by_limb(x, *y));
+        }
+
+        let mut quotient = vec![0; x.len()];
+        let mut remainder = Self::new(false, vec![]);
+
+        for index in (0..x.len() * LIMB_BITS).rev() {
+            remainder = Self::new(
+                false,
+                Self::add_magnitudes(&remainder.limbs, &remainder.limbs),
+            );
+
+            if x[index / LIMB_BITS] >> (index % LIMB_BITS) & 1 == 1 {
+                remainder = Self::new(false, Self::add_magnitudes(&remainder.limbs, &[1]));
+            }
+
+            if Self::compare_magnitudes(&remainder.limbs, y) != Ordering::Less {
+                remainder = Self::new(false, Self::subtract_magnitudes(&remainder.limbs, y));
+                quotient[index / LIMB_BITS] |= 1 << (index % LIMB_BITS);
+            }
+        }
+
+        Some((quotient, remainder.limbs))
+    }
+
+    fn divide_magnitude_by_limb(x: &[u32], y: u32) -> (Vec<u32>, Vec<u32>) {
+        let mut quotient = vec![0; x.len()];
+        let mut remainder = 0u64;
+
+        for (index, &limb) in x.iter().enumerate().rev() {
+            let dividend = remainder << LIMB_BITS | limb as u64;
+
+            quotient[index] = (dividend / y as u64) as _;
+            remainder = dividend % y as u64;
+        }
+
+        (quotient, vec![remainder as _])
+    }
+
+    fn limb(limbs: &[u32], index: usize) -> u64 {
+        limbs.get(index).copied().unwrap_or_default() as _
+    }
+}
+
+impl Ord for Bignum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => Self::compare_magnitudes(&self.limbs, &other.limbs),
+            (true, true) => Self::compare_magnitudes(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for Bignum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &Bignum {
+    type Output = Bignum;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.negative == other.negative {
+            Bignum::new(
+                self.negative,
+                Bignum::add_magnitudes(&self.limbs, &other.limbs),
+            )
+        } else if Bignum::compare_magnitudes(&self.limbs, &other.limbs) == Ordering::Less {
+            Bignum::new(
+                other.negative,
+                Bignum::subtract_magnitudes(&other.limbs, &self.limbs),
+            )
+        } else {
+            Bignum::new(
+                self.negative,
+                Bignum::subtract_magnitudes(&self.limbs, &other.limbs),
+            )
+        }
+    }
+}
+
+impl Sub for &Bignum {
+    type Output = Bignum;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + &-other
+    }
+}
+
+impl Mul for &Bignum {
+    type Output = Bignum;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Bignum::new(
+            self.negative != other.negative,
+            Bignum::multiply_magnitudes(&self.limbs, &other.limbs),
+        )
+    }
+}
+
+impl Neg for &Bignum {
+    type Output = Bignum;
+
+    fn neg(self) -> Self::Output {
+        Bignum::new(!self.negative, self.limbs.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTEGER_MAXIMUM_FIXNUM: i64 = i64::MAX >> 1;
+    // Fixnums of floating-point numbers represent integers exactly only in 53
+    // bits.
+    const MAXIMUM_FIXNUM: i64 =
+        if Number::from_i64(INTEGER_MAXIMUM_FIXNUM).to_i64() == INTEGER_MAXIMUM_FIXNUM {
+            INTEGER_MAXIMUM_FIXNUM
+        } else {
+            1 << f64::MANTISSA_DIGITS
+        };
+    const MINIMUM_FIXNUM: i64 = if MAXIMUM_FIXNUM == INTEGER_MAXIMUM_FIXNUM {
+        -MAXIMUM_FIXNUM - 1
+    } else {
+        -MAXIMUM_FIXNUM
+    };
+    const HEAP_SIZE: usize = 1 << 10;
+
+    fn bignum(number: i64) -> Bignum {
+        Bignum::from_i64(number)
+    }
+
+    #[test]
+    fn normalize_zero() {
+        assert_eq!(bignum(0), Bignum::new(true, vec![0, 0]));
+        assert_eq!(&bignum(1) - &bignum(1), bignum(0));
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(&bignum(1) + &bignum(2), bignum(3));
+        assert_eq!(&bignum(-1) + &bignum(2), bignum(1));
+        assert_eq!(&bignum(1) + &bignum(-2), bignum(-1));
+        assert_eq!(&bignum(-1) + &bignum(-2), bignum(-3));
+        assert_eq!(
+            &bignum(u32::MAX as _) + &bignum(1),
+            Bignum::new(false, vec![0, 1])
+        );
+    }
+
+    #[test]
+    fn add_large_numbers() {
+        assert_eq!(
+            &bignum(i64::MAX) + &bignum(i64::MAX),
+            Bignum::new(false, vec![u32::MAX - 1, u32::MAX, 0])
+        );
+    }
+
+    #[test]
+    fn subtract() {
+        assert_eq!(&bignum(3) - &bignum(2), bignum(1));
+        assert_eq!(&bignum(2) - &bignum(3), bignum(-1));
+        assert_eq!(&bignum(-2) - &bignum(3), bignum(-5));
+        assert_eq!(
+            &Bignum::new(false, vec![0, 1]) - &bignum(1),
+            bignum(u32::MAX as _)
+        );
+    }
+
+    #[test]
+    fn multiply() {
+        assert_eq!(&bignum(2) * &bignum(3), bignum(6));
+        assert_eq!(&bignum(-2) * &bignum(3), bignum(-6));
+        assert_eq!(&bignum(-2) * &bignum(-3), bignum(6));
+        assert_eq!(&bignum(0) * &bignum(-3), bignum(0));
+        assert_eq!(
+            &bignum(1 << 32) * &bignum(1 << 32),
+            Bignum::new(false, vec![0, 0, 1])
+        );
+    }
+
+    #[test]
+    fn divide() {
+        assert_eq!(bignum(7).checked_div(&bignum(2)), Some(bignum(3)));
+        assert_eq!(bignum(-7).checked_div(&bignum(2)), Some(bignum(-3)));
+        assert_eq!(bignum(7).checked_div(&bignum(-2)), Some(bignum(-3)));
+        assert_eq!(bignum(7).checked_div(&bignum(0)), None);
+    }
+
+    #[test]
+    fn divide_large_numbers() {
+        let x = &bignum(i64::MAX) * &bignum(i64::MAX);
+        let y = &bignum(i64::MAX) + &bignum(1);
+
+        assert_eq!(x.checked_div(&bignum(i64::MAX)), Some(bignum(i64::MAX)));
+        assert_eq!(x.checked_div(&y), Some(bignum(i64::MAX - 1)));
+        assert_eq!(x.checked_rem(&y), Some(bignum(1)));
+    }
+
+    #[test]
+    fn calculate_remainder() {
+        assert_eq!(bignum(7).checked_rem(&bignum(2)), Some(bignum(1)));
+        assert_eq!(bignum(-7).checked_rem(&bignum(2)), Some(bignum(-1)));
+        assert_eq!(bignum(7).checked_rem(&bignum(-2)), Some(bignum(1)));
+        assert_eq!(bignum(7).checked_rem(&bignum(0)), None);
+    }
+
+    #[test]
+    fn compare() {
+        assert!(bignum(1) < bignum(2));
+        assert!(bignum(-2) < bignum(-1));
+        assert!(bignum(-1) < bignum(1));
+        assert!(bignum(i64::MAX) < &bignum(i64::MAX) + &bignum(1));
+        assert!(-&(&bignum(i64::MAX) + &bignum(1)) < bignum(i64::MIN + 1));
+    }
+
+    #[test]
+    fn convert_to_number() {
+        assert_eq!(bignum(0).to_number(), Some(Number::from_i64(0)));
+        assert_eq!(bignum(-42).to_number(), Some(Number::from_i64(-42)));
+        assert_eq!(
+            bignum(MAXIMUM_FIXNUM).to_number(),
+            Some(Number::from_i64(MAXIMUM_FIXNUM))
+        );
+        assert_eq!(
+            bignum(MINIMUM_FIXNUM).to_number(),
+            Some(Number::from_i64(MINIMUM_FIXNUM))
+        );
+        assert_eq!(bignum(MAXIMUM_FIXNUM + 1).to_number(), None);
+        assert_eq!(bignum(MINIMUM_FIXNUM - 1).to_number(), None);
+    }
+
+    #[test]
+    fn convert_from_number() {
+        assert_eq!(Bignum::from_number(Number::from_i64(0)), Some(bignum(0)));
+        assert_eq!(
+            Bignum::from_number(Number::from_i64(-42)),
+            Some(bignum(-42))
+        );
+        assert_eq!(
+            Bignum::from_number(Number::from_i64(MAXIMUM_FIXNUM)),
+            Some(bignum(MAXIMUM_FIXNUM))
+        );
+        assert_eq!(
+            Bignum::from_number(Number::from_i64(MINIMUM_FIXNUM)),
+            Some(bignum(MINIMUM_FIXNUM))
+        );
+    }
+
+    #[test]
+    fn convert_from_floating_point_number() {
+        let number = Number::from_f64(2.5);
+
+        // Only fixnums of floating-point numbers can have fractions.
+        if number.to_f64() == 2.5 {
+            assert_eq!(Bignum::from_number(number), None);
+            assert_eq!(
+                Bignum::from_number(Number::from_f64(2f64.powi(64))),
+                Some(Bignum::new(false, vec![0, 0, 1]))
+            );
+            assert_eq!(
+                Bignum::from_number(Number::from_f64(-2f64.powi(63))),
+                Some(bignum(i64::MIN))
+            );
+        }
+    }
+
+    #[test]
+    fn convert_from_f64() {
+        assert_eq!(Bignum::from_f64(0.0), Some(bignum(0)));
+        assert_eq!(Bignum::from_f64(42.0), Some(bignum(42)));
+        assert_eq!(Bignum::from_f64(-2.5), Some(bignum(-2)));
+        assert_eq!(Bignum::from_f64(0.5), Some(bignum(0)));
+        assert_eq!(Bignum::from_f64(f64::MIN_POSITIVE), Some(bignum(0)));
+        assert_eq!(
+            Bignum::from_f64(2f64.powi(64)),
+            Some(Bignum::new(false, vec![0, 0, 1]))
+        );
+        assert_eq!(Bignum::from_f64(f64::INFINITY), None);
+        assert_eq!(Bignum::from_f64(f64::NAN), None);
+    }
+
+    #[cfg(feature = "flonum")]
+    #[test]
+    fn convert_to_f64() {
+        assert_eq!(bignum(0).to_f64(), 0.0);
+        assert_eq!(bignum(-42).to_f64(), -42.0);
+        assert_eq!(Bignum::new(false, vec![0, 0, 1]).to_f64(), 2f64.powi(64));
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        for x in [
+            bignum(0),
+            bignum(42),
+            bignum(MINIMUM_FIXNUM),
+            bignum(MAXIMUM_FIXNUM + 1),
+            bignum(i64::MIN),
+            &bignum(i64::MAX) * &bignum(i64::MAX),
+        ] {
+            let value = x.write(&mut memory).unwrap();
+
+            assert_eq!(Bignum::read(&memory, value).unwrap(), x);
+        }
+    }
+
+    #[test]
+    fn write_fixnum() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        assert_eq!(
+            bignum(42).write(&mut memory).unwrap(),
+            Number::from_i64(42).into()
+        );
+        assert!(bignum(MAXIMUM_FIXNUM + 1)
+            .write(&mut memory)
+            .unwrap()
+            .is_cons());
+    }
+}
@@ -8,8 +8,14 @@ use core::{
 pub enum Error {
     /// A device error.
     Device(stak_device::PrimitiveError),
+    /// A division by zero.
+    DivisionByZero,
     /// A halt of a virtual machine.
     Halt,
+    /// A non-finite number that cannot be exact.
+    NonFiniteNumber,
+    /// A number overflow.
+    NumberOverflow,
     /// A virtual machine error.
     Vm(stak_vm::Error),
 }
@@ -20,7 +26,10 @@ impl Display for Error {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
             Self::Device(error) => write!(formatter, "{error}"),
+            Self::DivisionByZero => write!(formatter, "division by zero"),
             Self::Halt => write!(formatter, "halt"),
+            Self::NonFiniteNumber => write!(formatter, "non-finite number"),
+            Self::NumberOverflow => write!(formatter, "number overflow"),
             Self::Vm(error) => write!(formatter, "{error}"),
         }
     }
@@ -0,0 +1,222 @@
+use super::Exact;
+use core::cmp::Ordering;
+use stak_vm::{Error, Memory, Value};
+
+// A real number which is either exact or inexact.
+//
+// On heap, an inexact number is a boxed floating-point number with a tag of
+// `Type::Flonum` while exact numbers keep their own representations.
+#[derive(Clone, Debug)]
+pub enum Real {
+    Exact(Exact),
+    Inexact(f64),
+}
+
+impl Real {
+    // Reads an exact or inexact number on heap.
+    pub fn read(memory: &Memory, value: Value) -> Result<Self, Error> {
+        Ok(if let Some(number) = memory.flonum(value) {
+            Self::Inexact(number)
+        } else {
+            Self::Exact(Exact::read(memory, value)?)
+        })
+    }
+
+    // Writes a real number on heap.
+    pub fn write(&self, memory: &mut Memory) -> Result<Value, Error> {
+        match self {
+            Self::Exact(number) => number.write(memory),
+            Self::Inexact(number) => Ok(memory.allocate_flonum(*number)?.into()),
+        }
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.operate(other, Exact::checked_add, |x, y| x + y)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.operate(other, Exact::checked_sub, |x, y| x - y)
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        self.operate(other, Exact::checked_mul, |x, y| x * y)
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            // Without rational numbers, a division of exact integers with a
+            // remainder results in an inexact number.
+            (Self::Exact(x), Self::Exact(y))
+                if !cfg!(feature = "rational") && !x.checked_rem(y)?.is_zero() =>
+            {
+                Some(Self::Inexact(x.to_f64() / y.to_f64()))
+            }
+            _ => self.operate(other, Exact::checked_div, |x, y| x / y),
+        }
+    }
+
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        self.operate(other, Exact::checked_rem, |x, y| x % y)
+    }
+
+    // Converts a number into an exact one. It returns `None` if a number is
+    // not finite.
+    pub fn to_exact(&self) -> Option<Self> {
+        Some(match self {
+            Self::Exact(number) => Self::Exact(number.clone()),
+            Self::Inexact(number) => Self::Exact(Exact::from_f64(*number)?),
+        })
+    }
+
+    pub fn to_inexact(&self) -> Self {
+        Self::Inexact(self.to_f64())
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Self::Exact(number) => number.to_f64(),
+            Self::Inexact(number) => *number,
+        }
+    }
+
+    fn operate(
+        &self,
+        other: &Self,
+        operate_exact: fn(&Exact, &Exact) -> Option<Exact>,
+        operate_inexact: fn(f64, f64) -> f64,
+    ) -> Option<Self> {
+        match (self, other) {
+            (Self::Exact(x), Self::Exact(y)) => operate_exact(x, y).map(Self::Exact),
+            _ => Some(Self::Inexact(operate_inexact(
+                self.to_f64(),
+                other.to_f64(),
+            ))),
+        }
+    }
+}
+
+impl PartialEq for Real {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Exact(x), Self::Exact(y)) => Some(x.cmp(y)),
+            (Self::Exact(x), Self::Inexact(y)) => compare_exact(x, *y),
+            (Self::Inexact(x), Self::Exact(y)) => compare_exact(y, *x).map(Ordering::reverse),
+            (Self::Inexact(x), Self::Inexact(y)) => x.partial_cmp(y),
+        }
+    }
+}
+
+// Compares an exact number with a floating-point number without rounding the
+// exact one.
+fn compare_exact(x: &Exact, y: f64) -> Option<Ordering> {
+    if y.is_nan() {
+        return None;
+    } else if y.is_infinite() {
+        return Some(if y > 0.0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        });
+    }
+
+    // Without rational numbers, a conversion truncates a fraction.
+    let z = Exact::from_f64(y)?;
+
+    Some(x.cmp(&z).then_with(|| {
+        0.0.partial_cmp(&(y - z.to_f64()))
+            .unwrap_or(Ordering::Equal)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::bignum::Bignum, *};
+
+    const HEAP_SIZE: usize = 1 << 10;
+
+    #[cfg_attr(not(feature = "rational"), allow(clippy::useless_conversion))]
+    fn exact(number: i64) -> Real {
+        Real::Exact(Exact::from(Bignum::from_i64(number)))
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(exact(1).checked_add(&exact(2)), Some(exact(3)));
+        assert!(matches!(
+            exact(1).checked_add(&Real::Inexact(0.5)),
+            Some(Real::Inexact(1.5))
+        ));
+        assert!(matches!(
+            Real::Inexact(0.5).checked_add(&Real::Inexact(0.25)),
+            Some(Real::Inexact(0.75))
+        ));
+    }
+
+    #[test]
+    fn divide() {
+        assert!(matches!(
+            exact(6).checked_div(&exact(3)),
+            Some(Real::Exact(_))
+        ));
+        assert!(matches!(
+            exact(1).checked_div(&Real::Inexact(2.0)),
+            Some(Real::Inexact(0.5))
+        ));
+        assert_eq!(exact(1).checked_div(&exact(0)), None);
+        assert!(matches!(
+            Real::Inexact(1.0).checked_div(&exact(0)),
+            Some(Real::Inexact(f64::INFINITY))
+        ));
+    }
+
+    #[test]
+    fn compare() {
+        assert!(exact(1) < Real::Inexact(1.5));
+        assert!(Real::Inexact(0.5) < exact(1));
+        assert_eq!(exact(2), Real::Inexact(2.0));
+        assert_ne!(Real::Inexact(f64::NAN), Real::Inexact(f64::NAN));
+    }
+
+    #[test]
+    fn compare_exactly() {
+        let x = 1 << 53;
+
+        assert!(exact(x + 1) > Real::Inexact(x as f64));
+        assert!(Real::Inexact(x as f64) < exact(x + 1));
+        assert_ne!(exact(x + 1), Real::Inexact(x as f64));
+        assert!(exact(-2) < Real::Inexact(-1.5));
+        assert!(exact(-1) > Real::Inexact(-1.5));
+        assert!(exact(i64::MAX) < Real::Inexact(f64::INFINITY));
+        assert!(exact(i64::MIN) > Real::Inexact(f64::NEG_INFINITY));
+        assert_eq!(exact(0).partial_cmp(&Real::Inexact(f64::NAN)), None);
+    }
+
+    #[test]
+    fn convert_exactness() {
+        assert_eq!(Real::Inexact(2.0).to_exact(), Some(exact(2)));
+        assert!(matches!(
+            Real::Inexact(2.0).to_exact(),
+            Some(Real::Exact(_))
+        ));
+        assert!(matches!(exact(2).to_inexact(), Real::Inexact(2.0)));
+        assert_eq!(Real::Inexact(f64::INFINITY).to_exact(), None);
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        for x in [exact(42), Real::Inexact(-0.5), Real::Inexact(1e300)] {
+            let value = x.write(&mut memory).unwrap();
+
+            assert_eq!(Real::read(&memory, value).unwrap(), x);
+        }
+    }
+}
@@ -17,6 +17,26 @@ pub(super) enum Primitive {
     Remainder,
     Exponentiation,
     Logarithm,
+    Sine,
+    Cosine,
+    Tangent,
+    Arcsine,
+    Arccosine,
+    Arctangent,
+    SquareRoot,
+    IsFinite,
+    IsInfinite,
+    IsNan,
+    Floor,
+    Ceiling,
+    Round,
+    Truncate,
+    FloorQuotient,
+    FloorDivide,
+    TruncateDivide,
+    NumberEqual = 35,
+    Exact,
+    Inexact,
     Halt = 40,
     Null = 50,
     Pair,
@@ -57,6 +77,26 @@ impl Primitive {
     pub const REMAINDER: usize = Self::Remainder as _;
     pub const EXPONENTIATION: usize = Self::Exponentiation as _;
     pub const LOGARITHM: usize = Self::Logarithm as _;
+    pub const SINE: usize = Self::Sine as _;
+    pub const COSINE: usize = Self::Cosine as _;
+    pub const TANGENT: usize = Self::Tangent as _;
+    pub const ARCSINE: usize = Self::Arcsine as _;
+    pub const ARCCOSINE: usize = Self::Arccosine as _;
+    pub const ARCTANGENT: usize = Self::Arctangent as _;
+    pub const SQUARE_ROOT: usize = Self::SquareRoot as _;
+    pub const IS_FINITE: usize = Self::IsFinite as _;
+    pub const IS_INFINITE: usize = Self::IsInfinite as _;
+    pub const IS_NAN: usize = Self::IsNan as _;
+    pub const FLOOR: usize = Self::Floor as _;
+    pub const CEILING: usize = Self::Ceiling as _;
+    pub const ROUND: usize = Self::Round as _;
+    pub const TRUNCATE: usize = Self::Truncate as _;
+    pub const FLOOR_QUOTIENT: usize = Self::FloorQuotient as _;
+    pub const FLOOR_DIVIDE: usize = Self::FloorDivide as _;
+    pub const TRUNCATE_DIVIDE: usize = Self::TruncateDivide as _;
+    pub const NUMBER_EQUAL: usize = Self::NumberEqual as _;
+    pub const EXACT: usize = Self::Exact as _;
+    pub const INEXACT: usize = Self::Inexact as _;
     pub const HALT: usize = Self::Halt as _;
     pub const NULL: usize = Self::Null as _;
     pub const PAIR: usize = Self::Pair as _;
@@ -0,0 +1,315 @@
+use super::bignum::Bignum;
+use core::cmp::Ordering;
+use stak_vm::{Error, Memory, Type, Value};
+
+// An exact rational number.
+//
+// On heap, a rational number is a rib of a numerator and a singleton list of a
+// denominator with a tag of `Type::Rational`. A denominator is always greater
+// than one and coprime with a numerator. Integers are never represented as
+// rational numbers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rational {
+    numerator: Bignum,
+    denominator: Bignum,
+}
+
+impl Rational {
+    // Reads an integer or a rational number on heap.
+    pub fn read(memory: &Memory, value: Value) -> Result<Self, Error> {
+        if let Some(cons) = value.to_cons() {
+            if memory.cdr(cons).tag() == Type::Rational as _ {
+                return Ok(Self {
+                    numerator: Bignum::read(memory, memory.car(cons))?,
+                    denominator: Bignum::read(memory, memory.car_value(memory.cdr(cons)))?,
+                });
+            }
+        }
+
+        Ok(Self::from(Bignum::read(memory, value)?))
+    }
+
+    // Writes a rational number as an integer or a rational number on heap.
+    pub fn write(&self, memory: &mut Memory) -> Result<Value, Error> {
+        if self.denominator == Bignum::from_i64(1) {
+            return self.numerator.write(memory);
+        }
+
+        let denominator = self.denominator.write(memory)?;
+        let list = memory.cons(denominator, memory.null())?;
+
+        // Keep the list on a stack as garbage collection on allocation of a
+        // numerator can move it.
+        memory.push(list.into())?;
+        let numerator = self.numerator.write(memory)?;
+        let list = memory.pop();
+
+        Ok(memory
+            .allocate(numerator, list.set_tag(Type::Rational as _))?
+            .into())
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            &(&self.numerator * &other.denominator) + &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator,
+        )
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            &(&self.numerator * &other.denominator) - &(&other.numerator * &self.denominator),
+            &self.denominator * &other.denominator,
+        )
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            &self.numerator * &other.numerator,
+            &self.denominator * &other.denominator,
+        )
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            &self.numerator * &other.denominator,
+            &self.denominator * &other.numerator,
+        )
+    }
+
+    // Calculates a remainder of truncated division.
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        let quotient = self.checked_div(other)?;
+        let quotient = Self::from(quotient.numerator.checked_div(&quotient.denominator)?);
+
+        self.checked_sub(&other.checked_mul(&quotient)?)
+    }
+
+    #[cfg(feature = "flonum")]
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    // Converts a finite floating-point number into an exact rational number.
+    #[cfg(feature = "flonum")]
+    pub fn from_f64(number: f64) -> Option<Self> {
+        let (mantissa, exponent) = Bignum::decompose_f64(number)?;
+        let mantissa = Bignum::from_i64(mantissa);
+        let power = Bignum::power_of_two(exponent.unsigned_abs());
+
+        if exponent < 0 {
+            Self::new(mantissa, power)
+        } else {
+            Some(Self::from(&mantissa * &power))
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator.to_f64() / self.denominator.to_f64()
+    }
+
+    // Creates a rational number. It returns `None` if a denominator is zero.
+    fn new(numerator: Bignum, denominator: Bignum) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+
+        let divisor = Self::gcd(numerator.clone(), denominator.clone());
+        let numerator = numerator.checked_div(&divisor)?;
+        let denominator = denominator.checked_div(&divisor)?;
+
+        Some(if denominator.is_negative() {
+            Self {
+                numerator: -&numerator,
+                denominator: -&denominator,
+            }
+        } else {
+            Self {
+                numerator,
+                denominator,
+            }
+        })
+    }
+
+    fn gcd(mut x: Bignum, mut y: Bignum) -> Bignum {
+        while let Some(z) = x.checked_rem(&y) {
+            x = y;
+            y = z;
+        }
+
+        if x.is_negative() {
+            -&x
+        } else {
+            x
+        }
+    }
+}
+
+impl From<Bignum> for Rational {
+    fn from(numerator: Bignum) -> Self {
+        Self {
+            numerator,
+            denominator: Bignum::from_i64(1),
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stak_vm::Number;
+
+    const HEAP_SIZE: usize = 1 << 10;
+
+    fn rational(numerator: i64, denominator: i64) -> Rational {
+        Rational::new(Bignum::from_i64(numerator), Bignum::from_i64(denominator)).unwrap()
+    }
+
+    fn integer(number: i64) -> Rational {
+        Rational::from(Bignum::from_i64(number))
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(rational(2, 4), rational(1, 2));
+        assert_eq!(rational(2, -4), rational(-1, 2));
+        assert_eq!(rational(-2, -4), rational(1, 2));
+        assert_eq!(rational(4, 2), integer(2));
+        assert_eq!(rational(0, -3), integer(0));
+        assert_eq!(
+            Rational::new(Bignum::from_i64(1), Bignum::from_i64(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(
+            rational(1, 2).checked_add(&rational(1, 3)),
+            Some(rational(5, 6))
+        );
+        assert_eq!(
+            rational(1, 2).checked_add(&rational(1, 2)),
+            Some(integer(1))
+        );
+        assert_eq!(
+            integer(1).checked_add(&rational(-1, 3)),
+            Some(rational(2, 3))
+        );
+    }
+
+    #[test]
+    fn subtract() {
+        assert_eq!(
+            rational(1, 2).checked_sub(&rational(1, 3)),
+            Some(rational(1, 6))
+        );
+        assert_eq!(
+            rational(1, 3).checked_sub(&rational(1, 2)),
+            Some(rational(-1, 6))
+        );
+    }
+
+    #[test]
+    fn multiply() {
+        assert_eq!(
+            rational(2, 3).checked_mul(&rational(3, 4)),
+            Some(rational(1, 2))
+        );
+        assert_eq!(integer(3).checked_mul(&rational(1, 3)), Some(integer(1)));
+    }
+
+    #[test]
+    fn divide() {
+        assert_eq!(integer(1).checked_div(&integer(3)), Some(rational(1, 3)));
+        assert_eq!(integer(6).checked_div(&integer(-3)), Some(integer(-2)));
+        assert_eq!(
+            rational(1, 2).checked_div(&rational(-1, 4)),
+            Some(integer(-2))
+        );
+        assert_eq!(rational(1, 2).checked_div(&integer(0)), None);
+    }
+
+    #[test]
+    fn calculate_remainder() {
+        assert_eq!(integer(7).checked_rem(&integer(2)), Some(integer(1)));
+        assert_eq!(integer(-7).checked_rem(&integer(2)), Some(integer(-1)));
+        assert_eq!(
+            rational(7, 2).checked_rem(&integer(1)),
+            Some(rational(1, 2))
+        );
+        assert_eq!(
+            rational(-7, 2).checked_rem(&integer(1)),
+            Some(rational(-1, 2))
+        );
+        assert_eq!(rational(1, 2).checked_rem(&integer(0)), None);
+    }
+
+    #[test]
+    fn compare() {
+        assert!(rational(1, 3) < rational(1, 2));
+        assert!(rational(-1, 2) < rational(-1, 3));
+        assert!(rational(1, 2) < integer(1));
+        assert!(integer(0) < rational(1, 2));
+    }
+
+    #[cfg(feature = "flonum")]
+    #[test]
+    fn convert_from_f64() {
+        assert_eq!(Rational::from_f64(0.0), Some(integer(0)));
+        assert_eq!(Rational::from_f64(3.0), Some(integer(3)));
+        assert_eq!(Rational::from_f64(0.5), Some(rational(1, 2)));
+        assert_eq!(Rational::from_f64(-0.75), Some(rational(-3, 4)));
+        assert_eq!(Rational::from_f64(f64::NAN), None);
+    }
+
+    #[cfg(feature = "flonum")]
+    #[test]
+    fn convert_to_f64() {
+        assert_eq!(integer(3).to_f64(), 3.0);
+        assert_eq!(rational(-3, 4).to_f64(), -0.75);
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+        memory.set_stack(memory.null());
+        let large = Bignum::from_i64(i64::MAX);
+
+        for x in [
+            integer(42),
+            rational(1, 3),
+            rational(-5, 7),
+            Rational::new(&large * &large, Bignum::from_i64(3)).unwrap(),
+            Rational::new(Bignum::from_i64(3), &large * &large).unwrap(),
+        ] {
+            let value = x.write(&mut memory).unwrap();
+
+            assert_eq!(Rational::read(&memory, value).unwrap(), x);
+        }
+    }
+
+    #[test]
+    fn write_integer() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        assert_eq!(
+            rational(4, 2).write(&mut memory).unwrap(),
+            Number::from_i64(2).into()
+        );
+    }
+}
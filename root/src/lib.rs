@@ -74,7 +74,9 @@ pub mod device {
     //!     )?;
     //!
     //!     vm.initialize(bytecodes.iter().copied())?;
-    //!     vm.run()
+    //!     vm.run()?;
+    //!
+    //!     Ok(())
     //! }
     //! ```
 
@@ -203,7 +205,9 @@ pub mod vm {
     //!     // Initialize a virtual machine with bytecodes.
     //!     vm.initialize(bytecodes.iter().copied())?;
     //!     // Run bytecodes on a virtual machine.
-    //!     vm.run()
+    //!     vm.run()?;
+    //!
+    //!     Ok(())
     //! }
     //! ```
 
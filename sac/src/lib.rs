@@ -90,7 +90,9 @@ macro_rules! main {
 
             vm.initialize(include_r7rs!($path).iter().copied())?;
 
-            Ok(vm.run()?)
+            vm.run()?;
+
+            Ok(())
         }
     };
 }
@@ -25,6 +25,26 @@ done
 
 shift $(expr $OPTIND - 1)
 
+# Both rational numbers and flonums are built on bignums.
+case ,$features, in
+*,flonum,* | *,rational,*)
+  features=$features,bignum
+  ;;
+esac
+
+case $interpreter in
+*stak*)
+  for feature in bignum flonum rational; do
+    case ,$features, in
+    *,$feature,*) ;;
+    *)
+      tags="${tags:-not @none} and not @$feature"
+      ;;
+    esac
+  done
+  ;;
+esac
+
 cd $(dirname $0)/..
 
 brew install chibi-scheme gauche guile parallel
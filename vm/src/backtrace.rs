@@ -0,0 +1,223 @@
+use crate::{
+    cons::{Cons, NEVER},
+    instruction::Instruction,
+    memory::Memory,
+    StackSlot, Type,
+};
+use core::fmt::{self, Display, Formatter};
+
+/// A backtrace of procedure calls.
+///
+/// It iterates over frames from the innermost call to the outermost one. If a
+/// virtual machine stops at a call instruction, e.g. on an error, the first
+/// frame is the call.
+#[derive(Clone)]
+pub struct Backtrace<'a, 'b> {
+    memory: &'a Memory<'b>,
+    code: Option<Cons>,
+    stack: Cons,
+}
+
+impl<'a, 'b> Backtrace<'a, 'b> {
+    pub(crate) fn new(memory: &'a Memory<'b>) -> Self {
+        let code = memory.code();
+
+        Self {
+            memory,
+            code: (!code.raw_eq(NEVER) && code != memory.null() && is_call(memory, code))
+                .then_some(code),
+            stack: memory.stack(),
+        }
+    }
+}
+
+impl<'a, 'b> Iterator for Backtrace<'a, 'b> {
+    type Item = Frame<'a, 'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(code) = self.code.take() {
+            return Some(Frame::new(self.memory, code));
+        }
+
+        while !self.stack.raw_eq(NEVER) && self.stack != self.memory.null() {
+            let stack = self.stack;
+            self.stack = self.memory.cdr(stack).to_cons()?;
+
+            if self.stack.tag() == StackSlot::Frame as _ {
+                // (code . stack)
+                let continuation = self.memory.car(stack).to_cons()?;
+                let code = self.memory.car(continuation).to_cons()?;
+                self.stack = self.memory.cdr(continuation).to_cons()?;
+
+                // Skip implicit top-level frames.
+                if is_call(self.memory, code) {
+                    return Some(Frame::new(self.memory, code));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Display for Backtrace<'_, '_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        for (index, frame) in self.clone().enumerate() {
+            if index > 0 {
+                writeln!(formatter)?;
+            }
+
+            write!(formatter, "at {frame}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A frame in a backtrace.
+#[derive(Clone, Copy)]
+pub struct Frame<'a, 'b> {
+    memory: &'a Memory<'b>,
+    code: Cons,
+}
+
+impl<'a, 'b> Frame<'a, 'b> {
+    /// Creates a frame of a call instruction.
+    pub const fn new(memory: &'a Memory<'b>, code: Cons) -> Self {
+        Self { memory, code }
+    }
+
+    /// Returns a call instruction.
+    pub const fn code(&self) -> Cons {
+        self.code
+    }
+
+    /// Returns a name of a called procedure.
+    ///
+    /// It returns `None` if a procedure is not called by a global variable.
+    pub fn name(&self) -> Option<impl Iterator<Item = char> + 'a> {
+        let symbol = self.memory.car(self.code).to_cons()?;
+        let string = self.memory.cdr(symbol).to_cons()?;
+
+        if string.tag() != Type::Symbol as _ {
+            return None;
+        }
+
+        characters(self.memory, string)
+    }
+
+    /// Returns a source position of a call.
+    ///
+    /// It returns `None` unless bytecodes are compiled with debug information.
+    pub fn position(&self) -> Option<Position<'a, 'b>> {
+        let code = self.memory.cdr(self.code).to_cons()?;
+
+        if code == self.memory.null() {
+            return None;
+        }
+
+        // An instruction tag is on a `cdr` of an instruction.
+        if self.memory.cdr(code).to_cons()?.tag() != Instruction::NOP {
+            return None;
+        }
+
+        Position::new(self.memory, self.memory.car(code).to_cons()?)
+    }
+}
+
+impl Display for Frame<'_, '_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if let Some(name) = self.name() {
+            for character in name {
+                write!(formatter, "{character}")?;
+            }
+        } else {
+            write!(formatter, "<anonymous>")?;
+        }
+
+        if let Some(position) = self.position() {
+            write!(formatter, " ({position})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A source position.
+#[derive(Clone, Copy)]
+pub struct Position<'a, 'b> {
+    memory: &'a Memory<'b>,
+    file: Option<Cons>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a, 'b> Position<'a, 'b> {
+    // (file line column)
+    fn new(memory: &'a Memory<'b>, position: Cons) -> Option<Self> {
+        let file = memory.car(position).to_cons()?;
+        let position = memory.cdr(position).to_cons()?;
+        let line = memory.car(position).to_number()?;
+        let position = memory.cdr(position).to_cons()?;
+        let column = memory.car(position).to_number()?;
+
+        Some(Self {
+            memory,
+            file: (memory.cdr(file).to_cons()?.tag() == Type::String as _).then_some(file),
+            line: line.to_i64() as _,
+            column: column.to_i64() as _,
+        })
+    }
+
+    /// Returns a file name.
+    pub fn file(&self) -> Option<impl Iterator<Item = char> + 'a> {
+        characters(self.memory, self.file?)
+    }
+
+    /// Returns a line number.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns a column number.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for Position<'_, '_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if let Some(file) = self.file() {
+            for character in file {
+                write!(formatter, "{character}")?;
+            }
+
+            write!(formatter, ":")?;
+        }
+
+        write!(formatter, "{}:{}", self.line, self.column)
+    }
+}
+
+// Returns characters of a string.
+fn characters<'a>(memory: &'a Memory, string: Cons) -> Option<impl Iterator<Item = char> + 'a> {
+    let mut characters = memory.cdr(string).to_cons()?;
+
+    Some(core::iter::from_fn(move || {
+        if characters == memory.null() {
+            return None;
+        }
+
+        let character = memory.car(characters).to_number()?;
+        characters = memory.cdr(characters).to_cons()?;
+
+        Some(char::from_u32(character.to_i64() as _).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }))
+}
+
+fn is_call(memory: &Memory, code: Cons) -> bool {
+    memory
+        .cdr(code)
+        .to_cons()
+        .is_some_and(|cons| cons.tag() >= Instruction::CALL)
+}
@@ -12,16 +12,40 @@ pub enum Error {
     ConsExpected,
     /// An unexpected end of bytecodes.
     BytecodeEnd,
+    /// An illegal heap image detected.
+    IllegalImage,
     /// An illegal instruction detected.
     IllegalInstruction,
+    /// An illegal integer detected.
+    IllegalInteger,
+    /// An illegal magic number of bytecodes detected.
+    IllegalMagicNumber,
     /// An illegal primitive detected.
     IllegalPrimitive,
+    /// An illegal program detected.
+    IllegalProgram,
+    /// An illegal share index detected.
+    IllegalShareIndex,
+    /// An illegal tag detected.
+    IllegalTag,
+    /// Incompatible fingerprints of bytecodes and a virtual machine.
+    IncompatibleFingerprint,
+    /// An incompatible number representation of bytecodes.
+    IncompatibleNumberRepresentation,
+    /// An incompatible format version of bytecodes.
+    IncompatibleVersion,
+    /// A limit of the number of live conses exceeded.
+    LiveConsLimit,
     /// A number expected.
     NumberExpected,
     /// Out of memory.
     OutOfMemory,
     /// A procedure expected.
     ProcedureExpected,
+    /// A program not finished.
+    ProgramNotFinished,
+    /// A stack underflow.
+    StackUnderflow,
 }
 
 impl error::Error for Error {}
@@ -32,11 +56,25 @@ impl Display for Error {
             Self::ArgumentCount => write!(formatter, "invalid argument count"),
             Self::BytecodeEnd => write!(formatter, "unexpected end of bytecodes"),
             Self::ConsExpected => write!(formatter, "cons expected"),
+            Self::IllegalImage => write!(formatter, "illegal heap image"),
             Self::IllegalInstruction => write!(formatter, "illegal instruction"),
+            Self::IllegalInteger => write!(formatter, "illegal integer"),
+            Self::IllegalMagicNumber => write!(formatter, "illegal magic number"),
             Self::IllegalPrimitive => write!(formatter, "illegal primitive"),
+            Self::IllegalProgram => write!(formatter, "illegal program"),
+            Self::IllegalShareIndex => write!(formatter, "illegal share index"),
+            Self::IllegalTag => write!(formatter, "illegal tag"),
+            Self::IncompatibleFingerprint => write!(formatter, "incompatible fingerprint"),
+            Self::IncompatibleNumberRepresentation => {
+                write!(formatter, "incompatible number representation")
+            }
+            Self::IncompatibleVersion => write!(formatter, "incompatible bytecode version"),
+            Self::LiveConsLimit => write!(formatter, "live cons limit exceeded"),
             Self::NumberExpected => write!(formatter, "number expected"),
             Self::OutOfMemory => write!(formatter, "out of memory"),
             Self::ProcedureExpected => write!(formatter, "procedure expected"),
+            Self::ProgramNotFinished => write!(formatter, "program not finished"),
+            Self::StackUnderflow => write!(formatter, "stack underflow"),
         }
     }
 }
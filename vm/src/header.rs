@@ -0,0 +1,258 @@
+use crate::Error;
+
+const FLOAT_FLAG: u8 = 1;
+const FINGERPRINT_FLAG: u8 = 2;
+const BIGNUM_FLAG: u8 = 4;
+const RATIONAL_FLAG: u8 = 8;
+
+/// A fingerprint of bytecodes.
+///
+/// It identifies, for example, a prelude or libraries which bytecodes are
+/// compiled against.
+pub type Fingerprint = [u8; 8];
+
+/// A header of bytecodes.
+///
+/// A header consists of a magic number, a format version, flags, and an
+/// optional fingerprint. Flags tell if bytecodes contain floating-point
+/// number, bignum, or rational number literals, which only virtual machines
+/// with the corresponding number representations can decode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Header {
+    float: bool,
+    bignum: bool,
+    rational: bool,
+    fingerprint: Option<Fingerprint>,
+}
+
+impl Header {
+    /// A magic number.
+    pub const MAGIC: [u8; 4] = [0x89, b'S', b'T', b'K'];
+    /// A format version.
+    pub const VERSION: u8 = 1;
+
+    /// Creates a header.
+    pub const fn new(fingerprint: Option<Fingerprint>) -> Self {
+        Self {
+            float: false,
+            bignum: false,
+            rational: false,
+            fingerprint,
+        }
+    }
+
+    /// Sets a flag of floating-point number literals.
+    pub const fn with_float(self, float: bool) -> Self {
+        Self { float, ..self }
+    }
+
+    /// Sets a flag of bignum literals.
+    pub const fn with_bignum(self, bignum: bool) -> Self {
+        Self { bignum, ..self }
+    }
+
+    /// Sets a flag of rational number literals.
+    pub const fn with_rational(self, rational: bool) -> Self {
+        Self { rational, ..self }
+    }
+
+    /// Returns `true` if bytecodes contain floating-point number literals.
+    pub const fn float(&self) -> bool {
+        self.float
+    }
+
+    /// Returns `true` if bytecodes contain bignum literals.
+    pub const fn bignum(&self) -> bool {
+        self.bignum
+    }
+
+    /// Returns `true` if bytecodes contain rational number literals.
+    pub const fn rational(&self) -> bool {
+        self.rational
+    }
+
+    /// Returns a fingerprint.
+    pub const fn fingerprint(&self) -> Option<&Fingerprint> {
+        self.fingerprint.as_ref()
+    }
+
+    /// Decodes a header.
+    pub fn decode(input: &mut impl Iterator<Item = u8>) -> Result<Self, Error> {
+        let mut read = || input.next().ok_or(Error::BytecodeEnd);
+
+        for byte in Self::MAGIC {
+            if read()? != byte {
+                return Err(Error::IllegalMagicNumber);
+            }
+        }
+
+        if read()? != Self::VERSION {
+            return Err(Error::IncompatibleVersion);
+        }
+
+        let flags = read()?;
+
+        if flags & !(FLOAT_FLAG | FINGERPRINT_FLAG | BIGNUM_FLAG | RATIONAL_FLAG) != 0 {
+            return Err(Error::IncompatibleVersion);
+        }
+
+        Ok(Self {
+            float: flags & FLOAT_FLAG != 0,
+            bignum: flags & BIGNUM_FLAG != 0,
+            rational: flags & RATIONAL_FLAG != 0,
+            fingerprint: if flags & FINGERPRINT_FLAG == 0 {
+                None
+            } else {
+                let mut fingerprint = Fingerprint::default();
+
+                for byte in &mut fingerprint {
+                    *byte = read()?;
+                }
+
+                Some(fingerprint)
+            },
+        })
+    }
+
+    /// Encodes a header.
+    pub fn encode(&self) -> impl Iterator<Item = u8> {
+        Self::MAGIC
+            .into_iter()
+            .chain([
+                Self::VERSION,
+                if self.float { FLOAT_FLAG } else { 0 }
+                    | if self.fingerprint.is_some() {
+                        FINGERPRINT_FLAG
+                    } else {
+                        0
+                    }
+                    | if self.bignum { BIGNUM_FLAG } else { 0 }
+                    | if self.rational { RATIONAL_FLAG } else { 0 },
+            ])
+            .chain(self.fingerprint.into_iter().flatten())
+    }
+
+    /// Checks if bytecodes are compatible with a virtual machine.
+    ///
+    /// Bytecodes with floating-point number literals are incompatible with
+    /// virtual machines without floating-point numbers regardless of the
+    /// other number representations. So are ones with bignum or rational
+    /// number literals with virtual machines without bignums or rational
+    /// numbers. If a virtual machine has a fingerprint, bytecodes must have
+    /// the same one.
+    pub fn check(&self, fingerprint: Option<&Fingerprint>) -> Result<(), Error> {
+        if self.float && !cfg!(any(feature = "float", feature = "flonum"))
+            || self.bignum && !cfg!(feature = "bignum")
+            || self.rational && !cfg!(feature = "rational")
+        {
+            Err(Error::IncompatibleNumberRepresentation)
+        } else if fingerprint.is_some_and(|expected| self.fingerprint.as_ref() != Some(expected)) {
+            Err(Error::IncompatibleFingerprint)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn encode_and_decode() {
+        for header in [
+            Header::default(),
+            Header::default().with_float(true),
+            Header::default().with_bignum(true),
+            Header::default().with_rational(true),
+            Header::new(Some([1, 2, 3, 4, 5, 6, 7, 8])),
+        ] {
+            assert_eq!(
+                Header::decode(&mut header.encode().collect::<Vec<_>>().into_iter()),
+                Ok(header)
+            );
+        }
+    }
+
+    #[test]
+    fn decode_illegal_magic_number() {
+        assert_eq!(
+            Header::decode(&mut [0, 0, 0, 0, 1, 0].into_iter()),
+            Err(Error::IllegalMagicNumber)
+        );
+    }
+
+    #[test]
+    fn decode_incompatible_version() {
+        assert_eq!(
+            Header::decode(&mut Header::MAGIC.into_iter().chain([0, 0])),
+            Err(Error::IncompatibleVersion)
+        );
+        assert_eq!(
+            Header::decode(&mut Header::MAGIC.into_iter().chain([Header::VERSION, 0xff])),
+            Err(Error::IncompatibleVersion)
+        );
+    }
+
+    #[test]
+    fn decode_truncated_header() {
+        assert_eq!(
+            Header::decode(&mut Header::MAGIC.into_iter()),
+            Err(Error::BytecodeEnd)
+        );
+        assert_eq!(
+            Header::decode(&mut Header::MAGIC.into_iter().chain([
+                Header::VERSION,
+                FINGERPRINT_FLAG,
+                0
+            ])),
+            Err(Error::BytecodeEnd)
+        );
+    }
+
+    #[test]
+    fn check_number_representation() {
+        assert_eq!(
+            Header::default().with_float(true).check(None),
+            if cfg!(any(feature = "float", feature = "flonum")) {
+                Ok(())
+            } else {
+                Err(Error::IncompatibleNumberRepresentation)
+            }
+        );
+        assert_eq!(
+            Header::default().with_bignum(true).check(None),
+            if cfg!(feature = "bignum") {
+                Ok(())
+            } else {
+                Err(Error::IncompatibleNumberRepresentation)
+            }
+        );
+        assert_eq!(
+            Header::default().with_rational(true).check(None),
+            if cfg!(feature = "rational") {
+                Ok(())
+            } else {
+                Err(Error::IncompatibleNumberRepresentation)
+            }
+        );
+        assert_eq!(Header::default().check(None), Ok(()));
+    }
+
+    #[test]
+    fn check_fingerprint() {
+        let header = Header::new(Some([1; 8]));
+
+        assert_eq!(header.check(None), Ok(()));
+        assert_eq!(header.check(Some(&[1; 8])), Ok(()));
+        assert_eq!(
+            header.check(Some(&[2; 8])),
+            Err(Error::IncompatibleFingerprint)
+        );
+        assert_eq!(
+            Header::default().check(Some(&[1; 8])),
+            Err(Error::IncompatibleFingerprint)
+        );
+    }
+}
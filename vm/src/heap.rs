@@ -0,0 +1,179 @@
+use crate::value::Value;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+const SIZE_UNIT: usize = 4;
+#[cfg(feature = "alloc")]
+const DEFAULT_GROWTH_RATIO: f64 = 0.5;
+
+// A heap of a memory.
+pub enum Heap<'a> {
+    Fixed(&'a mut [Value]),
+    #[cfg(feature = "alloc")]
+    Growable(Vec<Value>, GrowableHeap),
+}
+
+impl Heap<'_> {
+    #[inline]
+    pub const fn as_slice(&self) -> &[Value] {
+        match self {
+            Self::Fixed(heap) => heap,
+            #[cfg(feature = "alloc")]
+            Self::Growable(heap, _) => heap.as_slice(),
+        }
+    }
+
+    #[inline]
+    pub const fn as_mut_slice(&mut self) -> &mut [Value] {
+        match self {
+            Self::Fixed(heap) => heap,
+            #[cfg(feature = "alloc")]
+            Self::Growable(heap, _) => heap.as_mut_slice(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn resize(&mut self, size: usize) {
+        if let Self::Growable(heap, _) = self {
+            heap.resize(size, Default::default());
+            heap.shrink_to_fit();
+        }
+    }
+}
+
+/// A configuration of a growable heap.
+///
+/// A memory with a growable heap doubles its semispaces after garbage
+/// collection when a ratio of live data to a space exceeds a growth ratio. It
+/// optionally halves them when the ratio falls below a shrink ratio. A heap
+/// size never exceeds a maximum size nor falls below an initial size.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrowableHeap {
+    initial_size: usize,
+    maximum_size: usize,
+    growth_ratio: f64,
+    shrink_ratio: Option<f64>,
+}
+
+#[cfg(feature = "alloc")]
+impl GrowableHeap {
+    /// Creates a configuration of a growable heap.
+    ///
+    /// Heap sizes are in values and rounded down to multiples of four values.
+    pub const fn new(initial_size: usize, maximum_size: usize) -> Self {
+        let initial_size = initial_size / SIZE_UNIT * SIZE_UNIT;
+        let maximum_size = maximum_size / SIZE_UNIT * SIZE_UNIT;
+
+        Self {
+            initial_size,
+            maximum_size: if maximum_size < initial_size {
+                initial_size
+            } else {
+                maximum_size
+            },
+            growth_ratio: DEFAULT_GROWTH_RATIO,
+            shrink_ratio: None,
+        }
+    }
+
+    /// Sets a ratio of live data to a space above which a heap grows.
+    pub const fn with_growth_ratio(self, ratio: f64) -> Self {
+        Self {
+            growth_ratio: ratio,
+            ..self
+        }
+    }
+
+    /// Sets a ratio of live data to a space below which a heap shrinks.
+    pub const fn with_shrink_ratio(self, ratio: f64) -> Self {
+        Self {
+            shrink_ratio: Some(ratio),
+            ..self
+        }
+    }
+
+    /// Returns an initial heap size.
+    pub const fn initial_size(&self) -> usize {
+        self.initial_size
+    }
+
+    /// Returns a maximum heap size.
+    pub const fn maximum_size(&self) -> usize {
+        self.maximum_size
+    }
+
+    /// Returns a growth ratio.
+    pub const fn growth_ratio(&self) -> f64 {
+        self.growth_ratio
+    }
+
+    /// Returns a shrink ratio.
+    pub const fn shrink_ratio(&self) -> Option<f64> {
+        self.shrink_ratio
+    }
+
+    // Returns a new heap size for a given size of live data in a space.
+    pub(crate) fn resize(&self, size: usize, live_size: usize) -> usize {
+        let space_size = (size / 2) as f64;
+
+        if live_size as f64 > space_size * self.growth_ratio && size < self.maximum_size {
+            (2 * size).min(self.maximum_size)
+        } else if self
+            .shrink_ratio
+            .is_some_and(|ratio| (live_size as f64) < space_size * ratio)
+            && size > self.initial_size
+        {
+            let new_size = (size / 2 / SIZE_UNIT * SIZE_UNIT).max(self.initial_size);
+
+            if live_size <= new_size / 2 {
+                new_size
+            } else {
+                size
+            }
+        } else {
+            size
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_sizes() {
+        let heap = GrowableHeap::new(10, 7);
+
+        assert_eq!(heap.initial_size(), 8);
+        assert_eq!(heap.maximum_size(), 8);
+    }
+
+    #[test]
+    fn grow() {
+        let heap = GrowableHeap::new(16, 64);
+
+        assert_eq!(heap.resize(16, 4), 16);
+        assert_eq!(heap.resize(16, 6), 32);
+        assert_eq!(heap.resize(32, 12), 64);
+        assert_eq!(heap.resize(64, 32), 64);
+    }
+
+    #[test]
+    fn shrink() {
+        let heap = GrowableHeap::new(16, 64).with_shrink_ratio(0.25);
+
+        assert_eq!(heap.resize(64, 8), 64);
+        assert_eq!(heap.resize(64, 6), 32);
+        assert_eq!(heap.resize(32, 2), 16);
+        assert_eq!(heap.resize(16, 0), 16);
+    }
+
+    #[test]
+    fn shrink_without_ratio() {
+        let heap = GrowableHeap::new(16, 64);
+
+        assert_eq!(heap.resize(64, 0), 64);
+    }
+}
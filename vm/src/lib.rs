@@ -44,31 +44,42 @@
 #![cfg_attr(all(doc, not(doctest)), feature(doc_auto_cfg))]
 #![no_std]
 
-#[cfg(test)]
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
 #[cfg(any(feature = "trace_instruction", test))]
 extern crate std;
 
+mod backtrace;
 mod code;
 mod cons;
 mod error;
+mod header;
+mod heap;
 mod instruction;
 mod memory;
 mod number;
 mod primitive_set;
 mod profiler;
 mod stack_slot;
+mod statistics;
+mod status;
 mod r#type;
 mod value;
 mod vm;
 
+pub use backtrace::{Backtrace, Frame, Position};
 pub use cons::{Cons, Tag};
 pub use error::Error;
+pub use header::{Fingerprint, Header};
+#[cfg(feature = "alloc")]
+pub use heap::GrowableHeap;
 pub use memory::Memory;
 pub use number::{Number, NumberRepresentation};
 pub use primitive_set::PrimitiveSet;
 pub use profiler::Profiler;
 pub use r#type::Type;
 pub use stack_slot::StackSlot;
+pub use statistics::GarbageCollectionStatistics;
+pub use status::Status;
 pub use value::Value;
 pub use vm::Vm;
@@ -1,13 +1,30 @@
+#[cfg(feature = "alloc")]
+use crate::heap::GrowableHeap;
 use crate::{
     cons::{Cons, Tag, NEVER},
+    heap::Heap,
+    instruction::Instruction,
     number::Number,
     r#type::Type,
-    value::Value,
+    statistics::GarbageCollectionStatistics,
+    value::{TypedValue, Value},
     Error,
 };
-use core::fmt::{self, Display, Formatter};
+use cfg_elif::expr::feature;
+use core::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
 
 const CONS_FIELD_COUNT: usize = 2;
+const IMAGE_MAGIC: [u8; 4] = [0x89, b'S', b'T', b'I'];
+const IMAGE_VERSION: u8 = 1;
+// A number representation of values in heap images.
+const IMAGE_NUMBER_REPRESENTATION: u8 =
+    cfg!(feature = "float") as u8 | (cfg!(feature = "flonum") as u8) << 1;
+// A maximum ratio of a remembered set to a space.
+#[cfg(feature = "gc_generational")]
+const REMEMBERED_SET_DIVISOR: usize = 4;
 
 macro_rules! assert_heap_access {
     ($self:expr, $index:expr) => {
@@ -43,12 +60,40 @@ pub struct Memory<'a> {
     register: Cons,
     allocation_index: usize,
     space: bool,
-    heap: &'a mut [Value],
+    suspended: bool,
+    garbage_collection_statistics: GarbageCollectionStatistics,
+    garbage_collection_hook: Option<&'a dyn Fn(&GarbageCollectionStatistics)>,
+    garbage_collection_clock: Option<&'a dyn Fn() -> Duration>,
+    live_cons_limit: Option<usize>,
+    // An allocation index where a young generation starts.
+    #[cfg(feature = "gc_generational")]
+    young_index: usize,
+    #[cfg(feature = "gc_generational")]
+    remembered_count: usize,
+    // A range of conses to copy in garbage collection.
+    #[cfg(feature = "gc_generational")]
+    copy_start: usize,
+    #[cfg(feature = "gc_generational")]
+    copy_end: usize,
+    heap: Heap<'a>,
 }
 
 impl<'a> Memory<'a> {
     /// Creates a memory.
     pub fn new(heap: &'a mut [Value]) -> Result<Self, Error> {
+        Self::with_heap(Heap::Fixed(heap))
+    }
+
+    /// Creates a memory with a growable heap.
+    #[cfg(feature = "alloc")]
+    pub fn new_growable(heap: GrowableHeap) -> Result<Self, Error> {
+        Self::with_heap(Heap::Growable(
+            alloc::vec![Default::default(); heap.initial_size()],
+            heap,
+        ))
+    }
+
+    fn with_heap(heap: Heap<'a>) -> Result<Self, Error> {
         let mut memory = Self {
             code: NEVER,
             stack: NEVER,
@@ -56,6 +101,19 @@ impl<'a> Memory<'a> {
             register: NEVER,
             allocation_index: 0,
             space: false,
+            suspended: false,
+            garbage_collection_statistics: Default::default(),
+            garbage_collection_hook: None,
+            garbage_collection_clock: None,
+            live_cons_limit: None,
+            #[cfg(feature = "gc_generational")]
+            young_index: 0,
+            #[cfg(feature = "gc_generational")]
+            remembered_count: 0,
+            #[cfg(feature = "gc_generational")]
+            copy_start: 0,
+            #[cfg(feature = "gc_generational")]
+            copy_end: 0,
             heap,
         };
 
@@ -124,6 +182,36 @@ impl<'a> Memory<'a> {
         self.r#false = cons;
     }
 
+    /// Suspends a virtual machine at the next instruction boundary.
+    #[inline]
+    pub const fn suspend(&mut self) {
+        self.suspended = true;
+    }
+
+    /// Clears a suspension request and returns `true` if any.
+    #[inline]
+    pub(crate) const fn resume(&mut self) -> bool {
+        let suspended = self.suspended;
+        self.suspended = false;
+        suspended
+    }
+
+    /// Calls a procedure in place of returning from a primitive.
+    ///
+    /// The procedure receives `arity` arguments at the top of a stack.
+    pub fn call(&mut self, procedure: Value, arity: usize) -> Result<(), Error> {
+        let operand = self.cons(procedure, self.null())?;
+        let call = self.allocate(
+            operand.into(),
+            self.cdr(self.code)
+                .set_tag(Instruction::CALL + 2 * arity as Tag),
+        )?;
+        // A primitive advances code to the call instruction on return.
+        self.code = self.cons(self.null().into(), call)?;
+
+        Ok(())
+    }
+
     /// Pushes a value to a stack.
     #[inline]
     pub fn push(&mut self, value: Value) -> Result<(), Error> {
@@ -222,7 +310,7 @@ impl<'a> Memory<'a> {
     /// Returns a heap size.
     #[inline]
     pub const fn size(&self) -> usize {
-        self.heap.len()
+        self.heap().len()
     }
 
     #[inline]
@@ -252,16 +340,69 @@ impl<'a> Memory<'a> {
         self.allocation_start() + self.allocation_index
     }
 
+    /// Returns the number of garbage collections.
+    #[inline]
+    pub const fn garbage_collection_count(&self) -> usize {
+        self.garbage_collection_statistics.count()
+    }
+
+    /// Returns statistics of garbage collection.
+    #[inline]
+    pub const fn garbage_collection_statistics(&self) -> &GarbageCollectionStatistics {
+        &self.garbage_collection_statistics
+    }
+
+    /// Sets a hook called after every garbage collection.
+    pub fn set_garbage_collection_hook(&mut self, hook: &'a dyn Fn(&GarbageCollectionStatistics)) {
+        self.garbage_collection_hook = Some(hook);
+    }
+
+    /// Sets a clock to measure time spent in garbage collections.
+    ///
+    /// A clock returns a monotonic time.
+    pub fn set_garbage_collection_clock(&mut self, clock: &'a dyn Fn() -> Duration) {
+        self.garbage_collection_clock = Some(clock);
+    }
+
+    /// Returns a limit of the number of live conses.
+    #[inline]
+    pub const fn live_cons_limit(&self) -> Option<usize> {
+        self.live_cons_limit
+    }
+
+    /// Sets a limit of the number of live conses.
+    ///
+    /// The limit is checked after every garbage collection, which fails with
+    /// [`Error::LiveConsLimit`] if more conses are live.
+    pub const fn set_live_cons_limit(&mut self, limit: usize) {
+        self.live_cons_limit = Some(limit);
+    }
+
+    /// Clears a limit of the number of live conses.
+    pub const fn clear_live_cons_limit(&mut self) {
+        self.live_cons_limit = None;
+    }
+
+    #[inline]
+    const fn heap(&self) -> &[Value] {
+        self.heap.as_slice()
+    }
+
+    #[inline]
+    const fn heap_mut(&mut self) -> &mut [Value] {
+        self.heap.as_mut_slice()
+    }
+
     #[inline]
     const fn get(&self, index: usize) -> Value {
         assert_heap_access!(self, index);
-        self.heap[index]
+        self.heap()[index]
     }
 
     #[inline]
     fn set(&mut self, index: usize, value: Value) {
         assert_heap_access!(self, index);
-        self.heap[index] = value
+        self.heap_mut()[index] = value
     }
 
     /// Returns a value of a `car` field in a cons.
@@ -278,12 +419,12 @@ impl<'a> Memory<'a> {
 
     #[inline]
     const fn unchecked_car(&self, cons: Cons) -> Value {
-        self.heap[cons.index()]
+        self.heap()[cons.index()]
     }
 
     #[inline]
     const fn unchecked_cdr(&self, cons: Cons) -> Value {
-        self.heap[cons.index() + 1]
+        self.heap()[cons.index() + 1]
     }
 
     /// Returns a value of a `car` field in a value assumed as a cons.
@@ -315,6 +456,9 @@ impl<'a> Memory<'a> {
 
     #[inline]
     fn set_field(&mut self, cons: Cons, index: usize, value: Value) {
+        #[cfg(feature = "gc_generational")]
+        self.remember(cons, value);
+
         self.set_raw_field(
             cons,
             index,
@@ -335,13 +479,13 @@ impl<'a> Memory<'a> {
     }
 
     #[inline]
-    fn set_unchecked_car(&mut self, cons: Cons, value: Value) {
-        self.heap[cons.index()] = value
+    const fn set_unchecked_car(&mut self, cons: Cons, value: Value) {
+        self.heap_mut()[cons.index()] = value
     }
 
     #[inline]
-    fn set_unchecked_cdr(&mut self, cons: Cons, value: Value) {
-        self.heap[cons.index() + 1] = value;
+    const fn set_unchecked_cdr(&mut self, cons: Cons, value: Value) {
+        self.heap_mut()[cons.index() + 1] = value;
     }
 
     /// Sets a value to a `car` field in a value assumed as a cons.
@@ -401,9 +545,360 @@ impl<'a> Memory<'a> {
         Ok(())
     }
 
+    /// Allocates a boxed floating-point number.
+    ///
+    /// A boxed number is a cons of its upper 32 bits and a singleton list of
+    /// its lower 32 bits with a tag of [`Type::Flonum`].
+    #[cfg(feature = "flonum")]
+    pub fn allocate_flonum(&mut self, number: f64) -> Result<Cons, Error> {
+        let bits = number.to_bits();
+        let list = self.cons(Number::from_i64(bits as u32 as _).into(), self.null())?;
+
+        self.allocate(
+            Number::from_i64((bits >> 32) as _).into(),
+            list.set_tag(Type::Flonum as _).into(),
+        )
+    }
+
+    /// Returns a floating-point number if a value is a boxed one.
+    #[cfg(feature = "flonum")]
+    pub fn flonum(&self, value: Value) -> Option<f64> {
+        let cons = value.to_cons()?;
+
+        (self.cdr(cons).tag() == Type::Flonum as _).then(|| {
+            f64::from_bits(
+                (self.car(cons).assume_number().to_i64() as u64) << 32
+                    | self.car_value(self.cdr(cons)).assume_number().to_i64() as u64,
+            )
+        })
+    }
+
+    // Image
+
+    /// Returns a heap image.
+    ///
+    /// An image starts with a magic number, a format version, and a number
+    /// representation of values. Then, it consists of the `code`, `stack`,
+    /// `register` and `false` fields, an allocation index, and values in the
+    /// current space, all encoded as little-endian 64-bit integers. Cons
+    /// indices are relative to the start of the space so that the image can be
+    /// restored into a heap of any size large enough to hold it.
+    pub fn image(&self) -> impl Iterator<Item = u8> + '_ {
+        IMAGE_MAGIC
+            .into_iter()
+            .chain([IMAGE_VERSION, IMAGE_NUMBER_REPRESENTATION])
+            .chain(
+                [self.code, self.stack, self.register, self.r#false]
+                    .into_iter()
+                    .map(|cons| self.export_value(cons.into()))
+                    .chain([self.allocation_index as u64])
+                    .chain(
+                        self.heap()[self.allocation_start()..self.allocation_end()]
+                            .iter()
+                            .map(|&value| self.export_value(value)),
+                    )
+                    .flat_map(u64::to_le_bytes),
+            )
+    }
+
+    /// Restores a heap image created by [`Memory::image`].
+    ///
+    /// On failure, the memory is left in an unspecified state.
+    pub fn restore(&mut self, image: impl IntoIterator<Item = u8>) -> Result<(), Error> {
+        let mut image = image.into_iter();
+
+        for byte in IMAGE_MAGIC {
+            if image.next() != Some(byte) {
+                return Err(Error::IllegalImage);
+            }
+        }
+
+        if image.next().ok_or(Error::IllegalImage)? != IMAGE_VERSION {
+            return Err(Error::IncompatibleVersion);
+        } else if image.next().ok_or(Error::IllegalImage)? != IMAGE_NUMBER_REPRESENTATION {
+            return Err(Error::IncompatibleNumberRepresentation);
+        }
+
+        let mut read = || {
+            let mut bytes = [0; 8];
+
+            for byte in &mut bytes {
+                *byte = image.next().ok_or(Error::IllegalImage)?;
+            }
+
+            Ok(u64::from_le_bytes(bytes))
+        };
+
+        let code = read()?;
+        let stack = read()?;
+        let register = read()?;
+        let r#false = read()?;
+        let allocation_index = read()? as usize;
+
+        if !allocation_index.is_multiple_of(CONS_FIELD_COUNT) {
+            return Err(Error::IllegalImage);
+        }
+
+        #[cfg(feature = "alloc")]
+        self.reserve(allocation_index);
+
+        if allocation_index > self.space_size() {
+            return Err(Error::OutOfMemory);
+        }
+
+        for index in 0..allocation_index {
+            self.heap_mut()[index] = Self::import_value(read()?, allocation_index)?;
+        }
+
+        if image.next().is_some() {
+            return Err(Error::IllegalImage);
+        }
+
+        let import_cons = |raw| {
+            Self::import_value(raw, allocation_index)?
+                .to_cons()
+                .ok_or(Error::IllegalImage)
+        };
+
+        self.code = import_cons(code)?;
+        self.stack = import_cons(stack)?;
+        self.register = import_cons(register)?;
+        self.r#false = import_cons(r#false)?;
+        self.allocation_index = allocation_index;
+        self.space = false;
+        self.suspended = false;
+        #[cfg(feature = "gc_generational")]
+        self.forget_generations();
+
+        Ok(())
+    }
+
+    fn export_value(&self, value: Value) -> u64 {
+        match value.to_cons() {
+            Some(cons) if !cons.raw_eq(NEVER) => Value::from(
+                Cons::new((cons.index() - self.allocation_start()) as u64).set_tag(cons.tag()),
+            ),
+            _ => value,
+        }
+        .to_raw()
+    }
+
+    const fn import_value(raw: u64, allocation_index: usize) -> Result<Value, Error> {
+        let value = Value::from_raw(raw);
+
+        match value.to_cons() {
+            Some(cons) if !cons.raw_eq(NEVER) && cons.index() >= allocation_index => {
+                Err(Error::IllegalImage)
+            }
+            _ => Ok(value),
+        }
+    }
+
+    // Verification
+
+    /// Verifies instructions reachable from a code.
+    ///
+    /// Instructions are shared among branches. So we mark visited ones and
+    /// keep branches yet to verify in the other half of a heap which is unused
+    /// until the next garbage collection.
+    pub(crate) fn verify_code(&mut self, code: Cons) -> Result<(), Error> {
+        // The verification overwrites a remembered set.
+        #[cfg(feature = "gc_generational")]
+        self.forget_generations();
+
+        let start = if self.space { 0 } else { self.space_size() };
+        let mut end =
+            start + (self.allocation_index / CONS_FIELD_COUNT).div_ceil(u64::BITS as usize);
+        let branch_start = end;
+
+        self.heap_mut()[start..end].fill(Value::from_raw(0));
+        self.push_branch(start, &mut end, code)?;
+
+        while end > branch_start {
+            end -= 1;
+            let mut code = self.heap()[end].assume_cons();
+
+            loop {
+                let operand = self.car(code);
+                let instruction = self.cdr(code).to_cons().ok_or(Error::IllegalInstruction)?;
+
+                match instruction.tag() {
+                    Instruction::CONSTANT => {
+                        if let Some(code) = self.procedure_code(operand)? {
+                            self.push_branch(start, &mut end, code)?;
+                        }
+                    }
+                    Instruction::GET | Instruction::SET => self.verify_operand(operand)?,
+                    Instruction::IF => {
+                        let code = operand.to_cons().ok_or(Error::IllegalInstruction)?;
+                        self.push_branch(start, &mut end, code)?;
+                    }
+                    Instruction::NOP => {}
+                    // The maximum tag is reserved for an internal use.
+                    tag if (Instruction::CALL..Tag::MAX).contains(&tag) => {
+                        self.verify_operand(operand)?
+                    }
+                    _ => return Err(Error::IllegalInstruction),
+                }
+
+                if instruction == self.null() || !self.mark_code(start, instruction)? {
+                    break;
+                }
+
+                code = instruction;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks if an operand is a stack index or a symbol.
+    fn verify_operand(&self, operand: Value) -> Result<(), Error> {
+        let valid = match operand.to_typed() {
+            TypedValue::Cons(symbol) => self.cdr(symbol).tag() == Type::Symbol as _,
+            TypedValue::Number(index) => {
+                index.to_i64() >= 0 && Number::from_i64(index.to_i64()) == index
+            }
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::IllegalInstruction)
+        }
+    }
+
+    // Returns an instruction list of a non-primitive procedure.
+    fn procedure_code(&self, value: Value) -> Result<Option<Cons>, Error> {
+        let Some(procedure) = value.to_cons() else {
+            return Ok(None);
+        };
+
+        if self.cdr(procedure).to_cons().map(Cons::tag) != Some(Type::Procedure as _) {
+            return Ok(None);
+        }
+
+        let Some(code) = self.car(procedure).to_cons() else {
+            return Ok(None);
+        };
+
+        if !self.car(code).is_number() {
+            return Err(Error::IllegalInstruction);
+        }
+
+        Ok(Some(
+            self.cdr(code).to_cons().ok_or(Error::IllegalInstruction)?,
+        ))
+    }
+
+    fn push_branch(&mut self, start: usize, end: &mut usize, code: Cons) -> Result<(), Error> {
+        if code == self.null() {
+            return Err(Error::IllegalInstruction);
+        } else if self.mark_code(start, code)? {
+            self.heap_mut()[*end] = code.into();
+            *end += 1;
+        }
+
+        Ok(())
+    }
+
+    // Marks an instruction and returns `true` if it is not visited yet.
+    fn mark_code(&mut self, start: usize, code: Cons) -> Result<bool, Error> {
+        let index = code
+            .index()
+            .checked_sub(self.allocation_start())
+            .filter(|&index| index < self.allocation_index)
+            .ok_or(Error::IllegalInstruction)?
+            / CONS_FIELD_COUNT;
+        let cell = &mut self.heap_mut()[start + index / u64::BITS as usize];
+        let mark = 1 << (index % u64::BITS as usize);
+        let marks = cell.to_raw();
+
+        if marks & mark != 0 {
+            return Ok(false);
+        }
+
+        *cell = Value::from_raw(marks | mark);
+
+        Ok(true)
+    }
+
     // Garbage collection
 
-    fn collect_garbages(&mut self, cons: Option<&mut Cons>) -> Result<(), Error> {
+    /// Collects garbages.
+    ///
+    /// A given cons is treated as a root and updated to point to its copy.
+    ///
+    /// A growable heap is resized after collection depending on a size of
+    /// live data. If a memory has a live cons limit, it fails when more conses
+    /// are live after collection.
+    pub fn collect_garbages(&mut self, cons: Option<&mut Cons>) -> Result<(), Error> {
+        let mut root = cons.as_deref().copied();
+        let start = self.garbage_collection_clock.map(|clock| clock());
+
+        self.garbage_collection_statistics
+            .start(self.allocation_index / CONS_FIELD_COUNT);
+
+        if !feature!(if ("gc_generational") {
+            // Old generations might have garbages.
+            self.collect_young_garbages(root.as_mut())? && !self.is_over_live_cons_limit()
+        } else {
+            false
+        }) {
+            self.copy_garbages(root.as_mut())?;
+        }
+
+        #[cfg(feature = "alloc")]
+        self.resize(root.as_mut())?;
+
+        if let (Some(cons), Some(root)) = (cons, root) {
+            *cons = root;
+        }
+
+        if let (Some(clock), Some(start)) = (self.garbage_collection_clock, start) {
+            self.garbage_collection_statistics
+                .spend(clock().saturating_sub(start));
+        }
+
+        if let Some(hook) = self.garbage_collection_hook {
+            hook(&self.garbage_collection_statistics);
+        }
+
+        if self.is_over_live_cons_limit() {
+            return Err(Error::LiveConsLimit);
+        }
+
+        Ok(())
+    }
+
+    const fn is_over_live_cons_limit(&self) -> bool {
+        if let Some(limit) = self.live_cons_limit {
+            self.allocation_index / CONS_FIELD_COUNT > limit
+        } else {
+            false
+        }
+    }
+
+    fn copy_garbages(&mut self, cons: Option<&mut Cons>) -> Result<(), Error> {
+        self.copy_conses(cons)?;
+
+        self.garbage_collection_statistics.copy(
+            self.allocation_index / CONS_FIELD_COUNT,
+            self.allocation_index / CONS_FIELD_COUNT,
+        );
+
+        Ok(())
+    }
+
+    // Copies live conses into another space without counting them as garbage
+    // collection.
+    fn copy_conses(&mut self, cons: Option<&mut Cons>) -> Result<(), Error> {
+        #[cfg(feature = "gc_generational")]
+        {
+            self.copy_start = 0;
+            self.copy_end = self.size();
+        }
+
         self.allocation_index = 0;
         self.space = !self.space;
 
@@ -424,9 +919,208 @@ impl<'a> Memory<'a> {
             index += 1;
         }
 
+        // Every live cons gets old.
+        #[cfg(feature = "gc_generational")]
+        {
+            self.young_index = self.allocation_index;
+            self.remembered_count = 0;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn resize(&mut self, cons: Option<&mut Cons>) -> Result<(), Error> {
+        let Heap::Growable(heap, options) = &self.heap else {
+            return Ok(());
+        };
+        let size = options.resize(heap.len(), self.allocation_index);
+
+        if size > self.size() {
+            self.heap.resize(size);
+
+            if self.space {
+                // Live data are in the first half of a new heap. So we compact them
+                // into the second half.
+                self.space = false;
+                self.copy_conses(cons)?;
+            }
+        } else if size < self.size() {
+            if self.space {
+                // Move live data into the first half which a new heap keeps.
+                self.copy_conses(cons)?;
+            }
+
+            self.heap.resize(size);
+        }
+
         Ok(())
     }
 
+    // Grows a growable heap until its space gets large enough for a given size.
+    #[cfg(feature = "alloc")]
+    fn reserve(&mut self, space_size: usize) {
+        let Heap::Growable(heap, options) = &self.heap else {
+            return;
+        };
+        let mut size = heap.len();
+
+        while size / 2 < space_size && size < options.maximum_size() {
+            size = (2 * size).min(options.maximum_size());
+        }
+
+        self.heap.resize(size);
+    }
+
+    // Generational garbage collection
+    //
+    // A space consists of an old generation followed by a young one. Minor
+    // garbage collection copies live young conses into the other space and then
+    // moves them back to the end of the old generation. A write barrier
+    // remembers old conses pointing to young ones at the end of the other space.
+
+    #[cfg(feature = "gc_generational")]
+    const fn young_start(&self) -> usize {
+        self.allocation_start() + self.young_index
+    }
+
+    #[cfg(feature = "gc_generational")]
+    const fn remembered_end(&self) -> usize {
+        if self.space {
+            self.space_size()
+        } else {
+            self.size()
+        }
+    }
+
+    #[cfg(feature = "gc_generational")]
+    fn remember(&mut self, cons: Cons, value: Value) {
+        if cons.index() >= self.young_start()
+            || !value.to_cons().is_some_and(|value| {
+                !value.raw_eq(NEVER)
+                    && self.young_start() <= value.index()
+                    && value.index() < self.allocation_end()
+            })
+        {
+            return;
+        } else if self.remembered_count >= self.space_size() / REMEMBERED_SET_DIVISOR {
+            // Let every cons get young so that the next collection copies all.
+            self.forget_generations();
+            return;
+        }
+
+        self.remembered_count += 1;
+        let index = self.remembered_end() - self.remembered_count;
+        self.heap_mut()[index] = cons.into();
+    }
+
+    #[cfg(feature = "gc_generational")]
+    const fn forget_generations(&mut self) {
+        self.young_index = 0;
+        self.remembered_count = 0;
+    }
+
+    // Collects garbages in a young generation and returns `true`, or returns
+    // `false` if we need to collect garbages in a whole space.
+    #[cfg(feature = "gc_generational")]
+    fn collect_young_garbages(&mut self, mut cons: Option<&mut Cons>) -> Result<bool, Error> {
+        let young_size = self.allocation_index - self.young_index;
+
+        // Copied conses and a remembered set must not overlap in the other space.
+        if self.young_index == 0 || young_size + self.remembered_count > self.space_size() {
+            return Ok(false);
+        }
+
+        let old_end = self.young_start();
+        let remembered_end = self.remembered_end();
+        let remembered_start = remembered_end - self.remembered_count;
+
+        self.copy_start = old_end;
+        self.copy_end = self.allocation_end();
+        self.allocation_index = 0;
+        self.space = !self.space;
+
+        let copy_start = self.allocation_start();
+        let mut roots = [self.code, self.stack, self.r#false, self.register];
+
+        for root in &mut roots {
+            *root = self.copy_cons(*root)?;
+        }
+
+        if let Some(cons) = &mut cons {
+            **cons = self.copy_cons(**cons)?;
+        }
+
+        for index in remembered_start..remembered_end {
+            let remembered = self.heap()[index].assume_cons();
+
+            for field in 0..CONS_FIELD_COUNT {
+                let index = remembered.index() + field;
+                let value = self.copy_value(self.heap()[index])?;
+                self.heap_mut()[index] = value;
+            }
+        }
+
+        let mut index = copy_start;
+
+        while index < self.allocation_end() {
+            let value = self.copy_value(self.get(index))?;
+            self.set(index, value);
+            index += 1;
+        }
+
+        // Move copied conses back to the end of an old generation.
+        let copy_size = self.allocation_index;
+        let relocate = |value: Value| match value.to_cons() {
+            Some(cons)
+                if !cons.raw_eq(NEVER)
+                    && copy_start <= cons.index()
+                    && cons.index() < copy_start + copy_size =>
+            {
+                Cons::new((cons.index() - copy_start + old_end) as u64)
+                    .set_tag(cons.tag())
+                    .into()
+            }
+            _ => value,
+        };
+
+        self.heap_mut()
+            .copy_within(copy_start..copy_start + copy_size, old_end);
+
+        for index in old_end..old_end + copy_size {
+            self.heap_mut()[index] = relocate(self.heap()[index]);
+        }
+
+        for index in remembered_start..remembered_end {
+            let remembered = self.heap()[index].assume_cons();
+
+            for field in 0..CONS_FIELD_COUNT {
+                let index = remembered.index() + field;
+                self.heap_mut()[index] = relocate(self.heap()[index]);
+            }
+        }
+
+        [self.code, self.stack, self.r#false, self.register] =
+            roots.map(|root| relocate(root.into()).assume_cons());
+
+        if let Some(cons) = cons {
+            *cons = relocate((*cons).into()).assume_cons();
+        }
+
+        self.space = !self.space;
+        self.young_index += copy_size;
+        self.allocation_index = self.young_index;
+        self.remembered_count = 0;
+
+        self.garbage_collection_statistics.copy(
+            copy_size / CONS_FIELD_COUNT,
+            self.allocation_index / CONS_FIELD_COUNT,
+        );
+
+        // Collect garbages in a whole space if an old generation gets large.
+        Ok(self.allocation_index <= self.space_size() / 2)
+    }
+
     fn copy_value(&mut self, value: Value) -> Result<Value, Error> {
         Ok(if let Some(cons) = value.to_cons() {
             self.copy_cons(cons)?.into()
@@ -438,6 +1132,13 @@ impl<'a> Memory<'a> {
     fn copy_cons(&mut self, cons: Cons) -> Result<Cons, Error> {
         Ok(if cons.raw_eq(NEVER) {
             NEVER
+        } else if feature!(if ("gc_generational") {
+            cons.index() < self.copy_start || cons.index() >= self.copy_end
+        } else {
+            false
+        }) {
+            // Keep an old cons in minor garbage collection.
+            cons
         } else if self.unchecked_car(cons).raw_eq(NEVER.into()) {
             // Get a forward pointer.
             self.unchecked_cdr(cons).assume_cons()
@@ -501,7 +1202,7 @@ mod tests {
 
     macro_rules! assert_snapshot {
         ($memory:expr) => {
-            #[cfg(not(feature = "gc_always"))]
+            #[cfg(not(any(feature = "gc_always", feature = "gc_generational")))]
             insta::assert_snapshot!($memory);
 
             let _ = $memory;
@@ -569,6 +1270,30 @@ mod tests {
         assert_eq!(Value::from(memory.null()).to_cons().unwrap(), memory.null());
     }
 
+    #[cfg(feature = "flonum")]
+    #[test]
+    fn box_flonum() {
+        let mut heap = create_heap();
+        let mut memory = Memory::new(&mut heap).unwrap();
+
+        for number in [
+            0.0,
+            -0.0,
+            1.5,
+            -42.25,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::INFINITY,
+        ] {
+            let cons = memory.allocate_flonum(number).unwrap();
+
+            assert_eq!(memory.flonum(cons.into()), Some(number));
+        }
+
+        assert_eq!(memory.flonum(Number::from_i64(42).into()), None);
+        assert_eq!(memory.flonum(memory.null().into()), None);
+    }
+
     mod stack {
         use super::*;
 
@@ -597,32 +1322,255 @@ mod tests {
         }
     }
 
-    mod garbage_collection {
+    mod verification {
         use super::*;
 
-        #[test]
-        fn collect_cons() {
+        fn verify(
+            instruction: Tag,
+            operand: impl FnOnce(&mut Memory) -> Value,
+        ) -> Result<(), Error> {
             let mut heap = create_heap();
             let mut memory = Memory::new(&mut heap).unwrap();
 
-            memory
-                .allocate(Number::default().into(), Number::default().into())
-                .unwrap();
-            memory.collect_garbages(None).unwrap();
+            let operand = operand(&mut memory);
+            let code = memory.allocate(operand, memory.null().set_tag(instruction).into())?;
 
-            assert_snapshot!(memory);
+            memory.verify_code(code)
         }
 
-        #[test]
-        fn collect_stack() {
-            let mut heap = create_heap();
-            let mut memory = Memory::new(&mut heap).unwrap();
-
-            memory.stack = memory.null();
-            memory.push(Number::from_i64(42).into()).unwrap();
-            memory.collect_garbages(None).unwrap();
+        fn symbol(memory: &mut Memory) -> Value {
+            memory
+                .allocate(
+                    memory.boolean(false).into(),
+                    memory.null().set_tag(Type::Symbol as _).into(),
+                )
+                .unwrap()
+                .into()
+        }
 
-            assert_snapshot!(memory);
+        #[test]
+        fn verify_stack_index() {
+            for instruction in [Instruction::GET, Instruction::SET, Instruction::CALL + 2] {
+                assert_eq!(verify(instruction, |_| Number::from_i64(1).into()), Ok(()));
+            }
+        }
+
+        #[test]
+        fn verify_symbol() {
+            for instruction in [Instruction::GET, Instruction::SET, Instruction::CALL] {
+                assert_eq!(verify(instruction, symbol), Ok(()));
+            }
+        }
+
+        #[test]
+        fn reject_negative_stack_index() {
+            for instruction in [Instruction::GET, Instruction::SET, Instruction::CALL] {
+                assert_eq!(
+                    verify(instruction, |_| Number::from_i64(-1).into()),
+                    Err(Error::IllegalInstruction)
+                );
+            }
+        }
+
+        #[cfg(feature = "float")]
+        #[test]
+        fn reject_fractional_stack_index() {
+            assert_eq!(
+                verify(Instruction::GET, |_| Number::from_f64(0.5).into()),
+                Err(Error::IllegalInstruction)
+            );
+        }
+
+        #[test]
+        fn reject_non_symbol() {
+            for instruction in [Instruction::GET, Instruction::SET, Instruction::CALL] {
+                assert_eq!(
+                    verify(instruction, |memory| memory.null().into()),
+                    Err(Error::IllegalInstruction)
+                );
+            }
+        }
+
+        #[test]
+        fn reject_illegal_tag() {
+            assert_eq!(
+                verify(Tag::MAX, |_| Number::from_i64(0).into()),
+                Err(Error::IllegalInstruction)
+            );
+        }
+    }
+
+    mod image {
+        use super::*;
+        #[cfg(not(all(feature = "gc_always", feature = "gc_generational")))]
+        use alloc::string::ToString;
+        use alloc::vec::Vec;
+
+        #[test]
+        fn restore() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(1).into()).unwrap();
+            memory.push(Number::from_i64(2).into()).unwrap();
+
+            let image = memory.image().collect::<Vec<_>>();
+
+            let mut heap = create_heap();
+            let mut other = Memory::new(&mut heap).unwrap();
+            other.restore(image).unwrap();
+
+            // Minor garbage collection does not move conses into the other space.
+            #[cfg(not(all(feature = "gc_always", feature = "gc_generational")))]
+            assert_eq!(other.to_string(), memory.to_string());
+            assert_eq!(other.pop(), Number::from_i64(2).into());
+            assert_eq!(other.pop(), Number::from_i64(1).into());
+            assert_eq!(other.stack(), other.null());
+        }
+
+        #[test]
+        fn restore_after_garbage_collection() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(42).into()).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            let image = memory.image().collect::<Vec<_>>();
+
+            let mut heap = [Default::default(); 2 * HEAP_SIZE];
+            let mut other = Memory::new(&mut heap).unwrap();
+            other.restore(image).unwrap();
+
+            assert_eq!(other.allocation_start(), 0);
+            assert_eq!(other.allocation_index(), memory.allocation_index());
+            assert_eq!(other.pop(), Number::from_i64(42).into());
+            assert_eq!(other.stack(), other.null());
+            assert_eq!(other.car(other.boolean(false)), other.null().into());
+        }
+
+        #[test]
+        fn restore_truncated_image() {
+            let mut heap = create_heap();
+            let memory = Memory::new(&mut heap).unwrap();
+
+            let mut image = memory.image().collect::<Vec<_>>();
+            image.pop();
+
+            let mut heap = create_heap();
+            let mut other = Memory::new(&mut heap).unwrap();
+
+            assert_eq!(other.restore(image), Err(Error::IllegalImage));
+        }
+
+        #[test]
+        fn restore_image_with_trailing_bytes() {
+            let mut heap = create_heap();
+            let memory = Memory::new(&mut heap).unwrap();
+
+            let mut image = memory.image().collect::<Vec<_>>();
+            image.push(0);
+
+            let mut heap = create_heap();
+            let mut other = Memory::new(&mut heap).unwrap();
+
+            assert_eq!(other.restore(image), Err(Error::IllegalImage));
+        }
+
+        #[test]
+        fn restore_image_with_illegal_magic_number() {
+            let mut heap = create_heap();
+            let memory = Memory::new(&mut heap).unwrap();
+
+            let mut image = memory.image().collect::<Vec<_>>();
+            image[0] = 0;
+
+            let mut heap = create_heap();
+            let mut other = Memory::new(&mut heap).unwrap();
+
+            assert_eq!(other.restore(image), Err(Error::IllegalImage));
+        }
+
+        #[test]
+        fn restore_image_of_incompatible_version() {
+            let mut heap = create_heap();
+            let memory = Memory::new(&mut heap).unwrap();
+
+            let mut image = memory.image().collect::<Vec<_>>();
+            image[IMAGE_MAGIC.len()] = IMAGE_VERSION + 1;
+
+            let mut heap = create_heap();
+            let mut other = Memory::new(&mut heap).unwrap();
+
+            assert_eq!(other.restore(image), Err(Error::IncompatibleVersion));
+        }
+
+        #[test]
+        fn restore_image_of_incompatible_number_representation() {
+            let mut heap = create_heap();
+            let memory = Memory::new(&mut heap).unwrap();
+
+            let mut image = memory.image().collect::<Vec<_>>();
+            image[IMAGE_MAGIC.len() + 1] ^= 1;
+
+            let mut heap = create_heap();
+            let mut other = Memory::new(&mut heap).unwrap();
+
+            assert_eq!(
+                other.restore(image),
+                Err(Error::IncompatibleNumberRepresentation)
+            );
+        }
+
+        #[test]
+        fn restore_too_large_image() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+
+            for _ in 0..HEAP_SIZE / 8 {
+                memory.push(Default::default()).unwrap();
+            }
+
+            let image = memory.image().collect::<Vec<_>>();
+
+            let mut heap = [Default::default(); HEAP_SIZE / 4];
+            let mut other = Memory::new(&mut heap).unwrap();
+
+            assert_eq!(other.restore(image), Err(Error::OutOfMemory));
+        }
+    }
+
+    mod garbage_collection {
+        use super::*;
+        use core::cell::Cell;
+
+        #[test]
+        fn collect_cons() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory
+                .allocate(Number::default().into(), Number::default().into())
+                .unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            assert_snapshot!(memory);
+        }
+
+        #[test]
+        fn collect_stack() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(42).into()).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            assert_snapshot!(memory);
         }
 
         #[test]
@@ -638,6 +1586,130 @@ mod tests {
             assert_snapshot!(memory);
         }
 
+        #[cfg(not(any(feature = "gc_always", feature = "gc_generational")))]
+        #[test]
+        fn collect_statistics() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(1).into()).unwrap();
+            memory.push(Number::from_i64(2).into()).unwrap();
+            memory
+                .allocate(Number::default().into(), Number::default().into())
+                .unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            let statistics = *memory.garbage_collection_statistics();
+
+            assert_eq!(statistics.count(), 1);
+            assert_eq!(statistics.live_cons_count(), 4);
+            assert_eq!(statistics.copied_cons_count(), 4);
+            assert_eq!(statistics.peak_cons_count(), 5);
+
+            memory.stack = memory.null();
+            memory.collect_garbages(None).unwrap();
+
+            let statistics = memory.garbage_collection_statistics();
+
+            assert_eq!(statistics.count(), 2);
+            assert_eq!(statistics.live_cons_count(), 2);
+            assert_eq!(statistics.copied_cons_count(), 6);
+            assert_eq!(
+                statistics.copied_bytes(),
+                6 * 2 * core::mem::size_of::<Value>()
+            );
+            assert_eq!(statistics.peak_cons_count(), 5);
+        }
+
+        #[test]
+        fn call_hook() {
+            let count = Cell::new(0);
+            let hook = |statistics: &GarbageCollectionStatistics| count.set(statistics.count());
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.set_garbage_collection_hook(&hook);
+            memory.collect_garbages(None).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            assert_eq!(count.get(), 2);
+        }
+
+        #[test]
+        fn measure_time() {
+            let time = Cell::new(Duration::ZERO);
+            let clock = || {
+                time.set(time.get() + Duration::from_millis(1));
+                time.get()
+            };
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.set_garbage_collection_clock(&clock);
+            memory.collect_garbages(None).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            assert_eq!(
+                memory.garbage_collection_statistics().time(),
+                Duration::from_millis(2)
+            );
+        }
+
+        #[test]
+        fn collect_under_live_cons_limit() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.set_live_cons_limit(3);
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(42).into()).unwrap();
+
+            assert_eq!(memory.collect_garbages(None), Ok(()));
+        }
+
+        #[test]
+        fn collect_over_live_cons_limit() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(1).into()).unwrap();
+            memory.push(Number::from_i64(2).into()).unwrap();
+            memory.set_live_cons_limit(3);
+
+            assert_eq!(memory.collect_garbages(None), Err(Error::LiveConsLimit));
+        }
+
+        #[test]
+        fn collect_after_clearing_live_cons_limit() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(1).into()).unwrap();
+            memory.push(Number::from_i64(2).into()).unwrap();
+            memory.set_live_cons_limit(3);
+            memory.clear_live_cons_limit();
+
+            assert_eq!(memory.live_cons_limit(), None);
+            assert_eq!(memory.collect_garbages(None), Ok(()));
+        }
+
+        #[test]
+        fn allocate_over_live_cons_limit() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.set_live_cons_limit(HEAP_SIZE / 8);
+            memory.stack = memory.null();
+
+            assert_eq!(
+                (0..HEAP_SIZE).try_for_each(|_| memory.push(Default::default())),
+                Err(Error::LiveConsLimit)
+            );
+        }
+
         #[test]
         fn collect_cycle() {
             let mut heap = create_heap();
@@ -653,4 +1725,194 @@ mod tests {
             assert_snapshot!(memory);
         }
     }
+
+    #[cfg(feature = "alloc")]
+    mod growable_heap {
+        use super::*;
+        use alloc::vec::Vec;
+
+        #[test]
+        fn grow() {
+            let mut memory =
+                Memory::new_growable(GrowableHeap::new(HEAP_SIZE, 4 * HEAP_SIZE)).unwrap();
+
+            memory.stack = memory.null();
+
+            for _ in 0..HEAP_SIZE / 2 {
+                memory.push(Default::default()).unwrap();
+            }
+
+            assert!(memory.size() > HEAP_SIZE);
+
+            for _ in 0..HEAP_SIZE / 2 {
+                assert_eq!(memory.pop(), Default::default());
+            }
+
+            assert_eq!(memory.stack(), memory.null());
+        }
+
+        #[cfg(not(any(feature = "gc_always", feature = "gc_generational")))]
+        #[test]
+        fn count_copied_conses_once_on_growth() {
+            let mut memory =
+                Memory::new_growable(GrowableHeap::new(HEAP_SIZE, 4 * HEAP_SIZE)).unwrap();
+
+            memory.stack = memory.null();
+
+            while memory.allocation_index + CONS_FIELD_COUNT < memory.space_size() {
+                memory.push(Default::default()).unwrap();
+            }
+
+            memory.collect_garbages(None).unwrap();
+
+            let statistics = memory.garbage_collection_statistics();
+
+            assert!(memory.size() > HEAP_SIZE);
+            assert_eq!(statistics.count(), 1);
+            assert_eq!(statistics.copied_cons_count(), statistics.live_cons_count());
+        }
+
+        #[test]
+        fn fail_to_grow_over_maximum_size() {
+            let mut memory =
+                Memory::new_growable(GrowableHeap::new(HEAP_SIZE, 2 * HEAP_SIZE)).unwrap();
+
+            memory.stack = memory.null();
+
+            let result = (0..HEAP_SIZE).try_for_each(|_| memory.push(Default::default()));
+
+            assert_eq!(result, Err(Error::OutOfMemory));
+            assert_eq!(memory.size(), 2 * HEAP_SIZE);
+        }
+
+        // Minor garbage collection keeps garbages in an old generation.
+        #[cfg(not(feature = "gc_generational"))]
+        #[test]
+        fn shrink() {
+            let mut memory = Memory::new_growable(
+                GrowableHeap::new(HEAP_SIZE, 4 * HEAP_SIZE).with_shrink_ratio(0.25),
+            )
+            .unwrap();
+
+            memory.stack = memory.null();
+
+            for _ in 0..HEAP_SIZE / 2 {
+                memory.push(Default::default()).unwrap();
+            }
+
+            let size = memory.size();
+
+            memory.stack = memory.null();
+            memory.collect_garbages(None).unwrap();
+
+            assert!(memory.size() < size);
+            assert_eq!(memory.stack(), memory.null());
+            assert_eq!(memory.car(memory.boolean(false)), memory.null().into());
+        }
+
+        #[test]
+        fn restore_large_image() {
+            let mut heap = [Default::default(); 4 * HEAP_SIZE];
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+
+            for _ in 0..HEAP_SIZE / 2 {
+                memory.push(Number::from_i64(42).into()).unwrap();
+            }
+
+            let image = memory.image().collect::<Vec<_>>();
+
+            let mut other =
+                Memory::new_growable(GrowableHeap::new(HEAP_SIZE, 4 * HEAP_SIZE)).unwrap();
+            other.restore(image.iter().copied()).unwrap();
+
+            assert!(other.size() > HEAP_SIZE);
+            assert_eq!(other.image().collect::<Vec<_>>(), image);
+        }
+    }
+
+    #[cfg(feature = "gc_generational")]
+    mod generational_garbage_collection {
+        use super::*;
+
+        #[cfg(not(feature = "gc_always"))]
+        #[test]
+        fn collect_young_conses() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(1).into()).unwrap();
+            memory.collect_garbages(None).unwrap();
+            memory.push(Number::from_i64(2).into()).unwrap();
+            memory
+                .allocate(Number::default().into(), Number::default().into())
+                .unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            assert_eq!(
+                memory.garbage_collection_statistics().copied_cons_count(),
+                4
+            );
+            assert_eq!(memory.pop(), Number::from_i64(2).into());
+            assert_eq!(memory.pop(), Number::from_i64(1).into());
+            assert_eq!(memory.stack(), memory.null());
+        }
+
+        #[test]
+        fn remember_old_cons() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(Number::from_i64(1).into()).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            let cons = memory
+                .cons(Number::from_i64(2).into(), memory.null())
+                .unwrap();
+            memory.set_car(memory.stack(), cons.into());
+            memory.collect_garbages(None).unwrap();
+            memory.collect_garbages(None).unwrap();
+
+            let cons = memory.pop().assume_cons();
+
+            assert_eq!(memory.car(cons), Number::from_i64(2).into());
+            assert_eq!(memory.cdr(cons), memory.null().into());
+            assert_eq!(memory.stack(), memory.null());
+        }
+
+        #[test]
+        fn collect_many_conses() {
+            let mut heap = create_heap();
+            let mut memory = Memory::new(&mut heap).unwrap();
+
+            memory.stack = memory.null();
+            memory.push(memory.null().into()).unwrap();
+
+            for index in 0..HEAP_SIZE as i64 {
+                let cons = memory
+                    .cons(Number::from_i64(index).into(), memory.null())
+                    .unwrap();
+                let old = memory.top().assume_cons();
+
+                if index % 8 == 0 {
+                    memory.set_car(memory.stack(), cons.into());
+                } else {
+                    memory.set_cdr(cons, old.into());
+                    memory.set_car(memory.stack(), cons.into());
+                }
+            }
+
+            let mut list = memory.pop().assume_cons();
+
+            for index in (HEAP_SIZE as i64 - 8..HEAP_SIZE as i64).rev() {
+                assert_eq!(memory.car(list), Number::from_i64(index).into());
+                list = memory.cdr(list).assume_cons();
+            }
+
+            assert_eq!(list, memory.null());
+        }
+    }
 }
@@ -58,6 +58,92 @@ impl Number {
         self.to_representation() as _
     }
 
+    /// Adds numbers returning `None` on overflow.
+    ///
+    /// If the `float` feature is enabled, it never overflows.
+    #[inline]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        feature!(if ("float") {
+            Some(self + other)
+        } else {
+            Self::checked_new(
+                self.to_representation()
+                    .checked_add(other.to_representation())?,
+            )
+        })
+    }
+
+    /// Subtracts numbers returning `None` on overflow.
+    ///
+    /// If the `float` feature is enabled, it never overflows.
+    #[inline]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        feature!(if ("float") {
+            Some(self - other)
+        } else {
+            Self::checked_new(
+                self.to_representation()
+                    .checked_sub(other.to_representation())?,
+            )
+        })
+    }
+
+    /// Multiplies numbers returning `None` on overflow.
+    ///
+    /// If the `float` feature is enabled, it never overflows.
+    #[inline]
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        feature!(if ("float") {
+            Some(self * other)
+        } else {
+            Self::checked_new(
+                self.to_representation()
+                    .checked_mul(other.to_representation())?,
+            )
+        })
+    }
+
+    /// Divides numbers returning `None` on overflow or division by zero.
+    ///
+    /// If the `float` feature is enabled, it always succeeds.
+    #[inline]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        feature!(if ("float") {
+            Some(self / other)
+        } else {
+            Self::checked_new(
+                self.to_representation()
+                    .checked_div(other.to_representation())?,
+            )
+        })
+    }
+
+    /// Calculates a remainder returning `None` on overflow or division by
+    /// zero.
+    ///
+    /// If the `float` feature is enabled, it always succeeds.
+    #[inline]
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        feature!(if ("float") {
+            Some(self % other)
+        } else {
+            Self::checked_new(
+                self.to_representation()
+                    .checked_rem(other.to_representation())?,
+            )
+        })
+    }
+
+    #[cfg(not(feature = "float"))]
+    #[inline]
+    const fn checked_new(number: i64) -> Option<Self> {
+        if number << 1 >> 1 == number {
+            Some(Self::new(number))
+        } else {
+            None
+        }
+    }
+
     #[inline]
     pub(crate) const fn from_raw(raw: u64) -> Self {
         Self(feature!(if ("float") {
@@ -215,6 +301,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checked_add() {
+        assert_eq!(
+            Number::from_i64(1).checked_add(Number::from_i64(2)),
+            Some(Number::from_i64(3))
+        );
+    }
+
+    #[cfg(not(feature = "float"))]
+    #[test]
+    fn checked_add_overflow() {
+        let maximum = Number::from_i64(i64::MAX >> 1);
+
+        assert_eq!(maximum.checked_add(Number::from_i64(1)), None);
+        assert_eq!(
+            maximum.checked_add(Number::from_i64(-1)),
+            Some(Number::from_i64((i64::MAX >> 1) - 1))
+        );
+    }
+
+    #[cfg(not(feature = "float"))]
+    #[test]
+    fn checked_sub_overflow() {
+        let minimum = Number::from_i64(i64::MIN >> 1);
+
+        assert_eq!(minimum.checked_sub(Number::from_i64(1)), None);
+        assert!(minimum.checked_sub(Number::from_i64(-1)).is_some());
+    }
+
+    #[cfg(not(feature = "float"))]
+    #[test]
+    fn checked_mul_overflow() {
+        assert_eq!(
+            Number::from_i64(1 << 31).checked_mul(Number::from_i64(1 << 31)),
+            None
+        );
+        assert_eq!(
+            Number::from_i64(1 << 30).checked_mul(Number::from_i64(1 << 31)),
+            Some(Number::from_i64(1 << 61))
+        );
+    }
+
+    #[cfg(not(feature = "float"))]
+    #[test]
+    fn checked_div_overflow() {
+        assert_eq!(
+            Number::from_i64(i64::MIN >> 1).checked_div(Number::from_i64(-1)),
+            None
+        );
+        assert_eq!(Number::from_i64(1).checked_div(Number::default()), None);
+        assert_eq!(Number::from_i64(1).checked_rem(Number::default()), None);
+    }
+
     #[test]
     fn remainder() {
         assert_eq!(Number::default() % Number::from_i64(1), Number::default());
@@ -11,5 +11,18 @@ pub trait PrimitiveSet: Sized {
     type Error: From<Error> + error::Error;
 
     /// Runs a primitive on a virtual machine.
+    ///
+    /// A register of the memory holds a procedure of the primitive while it
+    /// runs.
     fn operate(&mut self, memory: &mut Memory, primitive: usize) -> Result<(), Self::Error>;
+
+    /// Handles garbage collection on a virtual machine.
+    ///
+    /// A virtual machine calls this method after every instruction during
+    /// which garbage collection happens.
+    fn collect_garbages(&mut self, memory: &Memory) -> Result<(), Self::Error> {
+        let _ = memory;
+
+        Ok(())
+    }
 }
@@ -10,4 +10,11 @@ pub trait Profiler {
 
     /// Profiles a call.
     fn profile_event(&mut self, name: &str);
+
+    /// Profiles garbage collection.
+    ///
+    /// A virtual machine calls this after an instruction during which garbage
+    /// collection happens. [`Memory::garbage_collection_statistics`] returns
+    /// its statistics.
+    fn profile_garbage_collection(&mut self, _memory: &Memory) {}
 }
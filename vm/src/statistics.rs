@@ -0,0 +1,78 @@
+use crate::value::Value;
+use core::{mem::size_of, time::Duration};
+
+const CONS_SIZE: usize = 2 * size_of::<Value>();
+
+/// Statistics of garbage collection.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GarbageCollectionStatistics {
+    count: usize,
+    copied_cons_count: usize,
+    live_cons_count: usize,
+    peak_cons_count: usize,
+    time: Duration,
+}
+
+impl GarbageCollectionStatistics {
+    /// Returns the number of garbage collections.
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the total number of conses copied by garbage collections.
+    pub const fn copied_cons_count(&self) -> usize {
+        self.copied_cons_count
+    }
+
+    /// Returns the total number of bytes copied by garbage collections.
+    pub const fn copied_bytes(&self) -> usize {
+        self.copied_cons_count * CONS_SIZE
+    }
+
+    /// Returns the number of live conses after the last garbage collection.
+    pub const fn live_cons_count(&self) -> usize {
+        self.live_cons_count
+    }
+
+    /// Returns the number of bytes of live conses after the last garbage
+    /// collection.
+    pub const fn live_bytes(&self) -> usize {
+        self.live_cons_count * CONS_SIZE
+    }
+
+    /// Returns the maximum number of conses allocated in a space before
+    /// garbage collections.
+    pub const fn peak_cons_count(&self) -> usize {
+        self.peak_cons_count
+    }
+
+    /// Returns the maximum number of bytes allocated in a space before garbage
+    /// collections.
+    pub const fn peak_bytes(&self) -> usize {
+        self.peak_cons_count * CONS_SIZE
+    }
+
+    /// Returns total time spent in garbage collections.
+    ///
+    /// It is measured only if a memory has a clock.
+    pub const fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub(crate) const fn start(&mut self, cons_count: usize) {
+        self.count = self.count.wrapping_add(1);
+
+        if cons_count > self.peak_cons_count {
+            self.peak_cons_count = cons_count;
+        }
+    }
+
+    pub(crate) const fn copy(&mut self, copied_cons_count: usize, live_cons_count: usize) {
+        self.copied_cons_count = self.copied_cons_count.wrapping_add(copied_cons_count);
+        self.live_cons_count = live_cons_count;
+    }
+
+    pub(crate) const fn spend(&mut self, time: Duration) {
+        self.time = self.time.saturating_add(time);
+    }
+}
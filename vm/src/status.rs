@@ -0,0 +1,10 @@
+/// A status of a virtual machine.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// A program is finished.
+    Finished,
+    /// A virtual machine ran out of fuel.
+    OutOfFuel,
+    /// A virtual machine is suspended.
+    Suspended,
+}
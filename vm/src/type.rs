@@ -24,4 +24,10 @@ pub enum Type {
     Record,
     /// A foreign object
     Foreign,
+    /// A bignum.
+    Bignum,
+    /// A rational number.
+    Rational,
+    /// A boxed floating-point number.
+    Flonum,
 }
@@ -105,6 +105,16 @@ impl Value {
         self.0 == value.0
     }
 
+    #[inline]
+    pub(crate) const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    #[inline]
+    pub(crate) const fn to_raw(self) -> u64 {
+        self.0
+    }
+
     const fn from_cons(cons: Cons) -> Self {
         Self(cons.to_raw())
     }
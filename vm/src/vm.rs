@@ -1,19 +1,30 @@
+#[cfg(feature = "alloc")]
+use crate::heap::GrowableHeap;
 #[cfg(feature = "profile")]
 use crate::profiler::Profiler;
 use crate::{
+    backtrace::Backtrace,
     code::{INTEGER_BASE, NUMBER_BASE, SHARE_BASE, TAG_BASE},
-    cons::{Cons, NEVER},
+    cons::{Cons, Tag, NEVER},
+    header::{Fingerprint, Header},
     instruction::Instruction,
     memory::Memory,
     number::Number,
     primitive_set::PrimitiveSet,
     r#type::Type,
+    statistics::GarbageCollectionStatistics,
+    status::Status,
     value::{TypedValue, Value},
     Error, StackSlot,
 };
+use cfg_elif::expr::feature;
 #[cfg(feature = "profile")]
 use core::cell::RefCell;
-use core::fmt::{self, Display, Formatter};
+use core::{
+    fmt::{self, Display, Formatter},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
 macro_rules! trace {
     ($prefix:literal, $data:expr) => {
@@ -46,6 +57,9 @@ struct Arity {
 pub struct Vm<'a, T: PrimitiveSet> {
     primitive_set: T,
     memory: Memory<'a>,
+    interrupt: Option<&'a AtomicBool>,
+    fingerprint: Option<Fingerprint>,
+    retain_program: bool,
     #[cfg(feature = "profile")]
     profiler: Option<RefCell<&'a mut dyn Profiler>>,
 }
@@ -58,11 +72,86 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
         Ok(Self {
             primitive_set,
             memory: Memory::new(heap)?,
+            interrupt: None,
+            fingerprint: None,
+            retain_program: false,
+            #[cfg(feature = "profile")]
+            profiler: None,
+        })
+    }
+
+    /// Creates a virtual machine with a growable heap.
+    #[cfg(feature = "alloc")]
+    pub fn new_growable(heap: GrowableHeap, primitive_set: T) -> Result<Self, Error> {
+        Ok(Self {
+            primitive_set,
+            memory: Memory::new_growable(heap)?,
+            interrupt: None,
+            fingerprint: None,
+            retain_program: false,
             #[cfg(feature = "profile")]
             profiler: None,
         })
     }
 
+    /// Sets an interrupt flag.
+    ///
+    /// When the flag is set, a virtual machine suspends its execution at the
+    /// next instruction boundary and clears the flag.
+    pub fn with_interrupt(self, interrupt: &'a AtomicBool) -> Self {
+        Self {
+            interrupt: Some(interrupt),
+            ..self
+        }
+    }
+
+    /// Sets a fingerprint.
+    ///
+    /// A virtual machine rejects bytecodes without the same fingerprint on
+    /// initialization.
+    pub fn with_fingerprint(self, fingerprint: Fingerprint) -> Self {
+        Self {
+            fingerprint: Some(fingerprint),
+            ..self
+        }
+    }
+
+    /// Retains a program after its execution.
+    ///
+    /// By default, a program is garbage-collected as it runs. Retain it if we
+    /// call procedures defined in the program by [`Vm::apply`].
+    pub fn with_retained_program(self) -> Self {
+        Self {
+            retain_program: true,
+            ..self
+        }
+    }
+
+    /// Sets a limit of the number of live conses.
+    ///
+    /// See [`Memory::set_live_cons_limit`] for details.
+    pub const fn with_live_cons_limit(mut self, limit: usize) -> Self {
+        self.memory.set_live_cons_limit(limit);
+        self
+    }
+
+    /// Sets a hook called after every garbage collection.
+    pub fn with_garbage_collection_hook(
+        mut self,
+        hook: &'a dyn Fn(&GarbageCollectionStatistics),
+    ) -> Self {
+        self.memory.set_garbage_collection_hook(hook);
+        self
+    }
+
+    /// Sets a clock to measure time spent in garbage collections.
+    ///
+    /// See [`Memory::set_garbage_collection_clock`] for details.
+    pub fn with_garbage_collection_clock(mut self, clock: &'a dyn Fn() -> Duration) -> Self {
+        self.memory.set_garbage_collection_clock(clock);
+        self
+    }
+
     /// Sets a profiler.
     #[cfg(feature = "profile")]
     pub fn with_profiler(self, profiler: &'a mut dyn Profiler) -> Self {
@@ -82,23 +171,198 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
         &mut self.primitive_set
     }
 
+    /// Returns a reference to a memory.
+    pub const fn memory(&self) -> &Memory<'a> {
+        &self.memory
+    }
+
+    /// Returns a mutable reference to a memory.
+    pub const fn memory_mut(&mut self) -> &mut Memory<'a> {
+        &mut self.memory
+    }
+
+    /// Returns `true` if a program is finished.
+    pub fn is_finished(&self) -> bool {
+        self.memory.code() == self.memory.null()
+    }
+
+    /// Returns a backtrace of procedure calls.
+    ///
+    /// It is useful to locate an error after [`Vm::run`] fails.
+    pub fn backtrace(&self) -> Backtrace<'_, 'a> {
+        Backtrace::new(&self.memory)
+    }
+
     /// Runs bytecodes on a virtual machine.
-    pub fn run(&mut self) -> Result<(), T::Error> {
-        while self.memory.code() != self.memory.null() {
-            let instruction = self.memory.cdr(self.memory.code()).assume_cons();
-
-            trace!("instruction", instruction.tag());
-
-            match instruction.tag() {
-                Instruction::CONSTANT => self.constant()?,
-                Instruction::GET => self.get()?,
-                Instruction::SET => self.set(),
-                Instruction::IF => self.r#if(),
-                Instruction::NOP => self.advance_code(),
-                code => self.call(instruction, code as usize - Instruction::CALL as usize)?,
+    ///
+    /// If the virtual machine is suspended by a primitive or an interrupt
+    /// flag, it returns [`Status::Suspended`]. Then, we can resume its
+    /// execution by calling this method again.
+    pub fn run(&mut self) -> Result<Status, T::Error> {
+        while !self.is_finished() {
+            self.execute()?;
+
+            if self.resume() {
+                return Ok(Status::Suspended);
+            }
+        }
+
+        Ok(Status::Finished)
+    }
+
+    /// Runs bytecodes on a virtual machine with a limited number of
+    /// instructions.
+    ///
+    /// If the virtual machine runs out of fuel, it returns
+    /// [`Status::OutOfFuel`]. Then, we can resume its execution by calling
+    /// this method again.
+    pub fn run_with_fuel(&mut self, mut fuel: usize) -> Result<Status, T::Error> {
+        while !self.is_finished() {
+            if fuel == 0 {
+                return Ok(Status::OutOfFuel);
+            }
+
+            self.execute()?;
+            fuel -= 1;
+
+            if self.resume() {
+                return Ok(Status::Suspended);
+            }
+        }
+
+        Ok(Status::Finished)
+    }
+
+    /// Applies a procedure to arguments and returns its result.
+    ///
+    /// The `push` function pushes arguments onto a stack and returns the number
+    /// of the arguments. A virtual machine must
+    /// have finished running a program retained by
+    /// [`Vm::with_retained_program`] before applying procedures. Suspension
+    /// requests are ignored during applications.
+    ///
+    /// On error, an application is discarded so that we can apply procedures
+    /// again.
+    pub fn apply(
+        &mut self,
+        procedure: Value,
+        push: impl FnOnce(&mut Memory<'a>, &mut T) -> Result<usize, T::Error>,
+    ) -> Result<Value, T::Error> {
+        if !self.is_finished() {
+            return Err(Error::ProgramNotFinished.into());
+        }
+
+        // Keep a procedure in a register as garbage collection on allocation of
+        // a frame can move it.
+        self.memory
+            .set_register(procedure.to_cons().ok_or(Error::ProcedureExpected)?);
+
+        // Initialize an implicit top-level frame below the procedure and arguments.
+        let codes = self
+            .memory
+            .cons(Number::default().into(), self.memory.null())?
+            .into();
+        let continuation = self.memory.cons(codes, self.memory.stack())?.into();
+        let frame = self.memory.allocate(
+            continuation,
+            self.memory.null().set_tag(StackSlot::Frame as _).into(),
+        )?;
+        self.memory.set_stack(frame);
+        self.memory.push(self.memory.register().into())?;
+        self.memory.set_register(NEVER);
+
+        let result = self.apply_procedure(push);
+
+        self.resume();
+
+        if result.is_err() {
+            self.abort_application();
+        }
+
+        result
+    }
+
+    fn apply_procedure(
+        &mut self,
+        push: impl FnOnce(&mut Memory<'a>, &mut T) -> Result<usize, T::Error>,
+    ) -> Result<Value, T::Error> {
+        let count = push(&mut self.memory, &mut self.primitive_set)?;
+
+        let code = self.memory.allocate(
+            Number::from_i64(count as _).into(),
+            self.memory
+                .null()
+                .set_tag(Instruction::CALL + 2 * count as Tag)
+                .into(),
+        )?;
+        self.memory.set_code(code);
+
+        while !self.is_finished() {
+            self.execute()?;
+        }
+
+        Ok(self.memory.pop())
+    }
+
+    // Restores a stack below the outermost frame of an application.
+    fn abort_application(&mut self) {
+        let mut stack = self.memory.stack();
+        let mut base = stack;
+
+        while stack != self.memory.null() {
+            let cdr = self.memory.cdr(stack).assume_cons();
+
+            stack = if cdr.tag() == StackSlot::Frame as _ {
+                base = self.memory.cdr_value(self.memory.car(stack)).assume_cons();
+                base
+            } else {
+                cdr
+            };
+        }
+
+        self.memory.set_stack(base);
+        self.memory.set_code(self.memory.null());
+    }
+
+    // Clears suspension requests and returns `true` if any.
+    #[inline]
+    fn resume(&mut self) -> bool {
+        // Avoid a read-modify-write operation on every instruction.
+        let interrupted = self.interrupt.is_some_and(|interrupt| {
+            let interrupted = interrupt.load(Ordering::Relaxed);
+
+            if interrupted {
+                interrupt.store(false, Ordering::Relaxed);
             }
 
-            trace_memory!(self);
+            interrupted
+        });
+
+        self.memory.resume() || interrupted
+    }
+
+    #[inline]
+    fn execute(&mut self) -> Result<(), T::Error> {
+        let garbage_collection_count = self.memory.garbage_collection_count();
+        let instruction = self.memory.cdr(self.memory.code()).assume_cons();
+
+        trace!("instruction", instruction.tag());
+
+        match instruction.tag() {
+            Instruction::CONSTANT => self.constant()?,
+            Instruction::GET => self.get()?,
+            Instruction::SET => self.set(),
+            Instruction::IF => self.r#if(),
+            Instruction::NOP => self.advance_code(),
+            code => self.call(instruction, code as usize - Instruction::CALL as usize)?,
+        }
+
+        trace_memory!(self);
+
+        if self.memory.garbage_collection_count() != garbage_collection_count {
+            #[cfg(feature = "profile")]
+            self.profile_garbage_collection();
+            self.primitive_set.collect_garbages(&self.memory)?;
         }
 
         Ok(())
@@ -154,15 +418,13 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
 
     fn call(&mut self, instruction: Cons, arity: usize) -> Result<(), T::Error> {
         let r#return = instruction == self.memory.null();
-        let procedure = self.procedure();
+        let Some(procedure) = self.procedure() else {
+            return Err(Error::ProcedureExpected.into());
+        };
 
         trace!("procedure", procedure);
         trace!("return", r#return);
 
-        if self.environment(procedure).tag() != Type::Procedure as _ {
-            return Err(Error::ProcedureExpected.into());
-        }
-
         match self.code(procedure).to_typed() {
             TypedValue::Cons(code) => {
                 #[cfg(feature = "profile")]
@@ -184,6 +446,21 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
                 } else {
                     self.memory.null()
                 };
+                let mut count = arguments.count.to_i64() as usize;
+                let mut rest = list;
+
+                while rest != self.memory.null() {
+                    count += 1;
+                    rest = self.memory.cdr(rest).assume_cons();
+                }
+
+                // Check an argument count before pushing a frame so that a backtrace
+                // points to a caller.
+                if count < parameters.count.to_i64() as usize
+                    || !parameters.variadic && count > parameters.count.to_i64() as usize
+                {
+                    return Err(Error::ArgumentCount.into());
+                }
 
                 for _ in 0..arguments.count.to_i64() {
                     let value = self.memory.pop();
@@ -215,10 +492,6 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
                 );
 
                 for _ in 0..parameters.count.to_i64() {
-                    if self.memory.register() == self.memory.null() {
-                        return Err(Error::ArgumentCount.into());
-                    }
-
                     self.memory.push(self.memory.car(self.memory.register()))?;
                     self.memory
                         .set_register(self.memory.cdr(self.memory.register()).assume_cons());
@@ -226,20 +499,33 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
 
                 if parameters.variadic {
                     self.memory.push(self.memory.register().into())?;
-                } else if self.memory.register() != self.memory.null() {
-                    return Err(Error::ArgumentCount.into());
                 }
             }
             TypedValue::Number(primitive) => {
+                // Keep a procedure in a register while a primitive runs.
                 if Self::parse_arity(arity).variadic {
                     let list = self.memory.pop().assume_cons();
+                    let list = self.memory.cons(procedure.into(), list)?;
                     self.memory.set_register(list);
 
-                    while self.memory.register() != self.memory.null() {
-                        self.memory.push(self.memory.car(self.memory.register()))?;
-                        self.memory
-                            .set_register(self.memory.cdr(self.memory.register()).assume_cons());
+                    loop {
+                        let list = self.memory.cdr(self.memory.register()).assume_cons();
+
+                        if list == self.memory.null() {
+                            break;
+                        }
+
+                        self.memory.push(self.memory.car(list))?;
+                        let list = self
+                            .memory
+                            .cdr(self.memory.cdr(self.memory.register()).assume_cons());
+                        self.memory.set_cdr(self.memory.register(), list);
                     }
+
+                    self.memory
+                        .set_register(self.memory.car(self.memory.register()).assume_cons());
+                } else {
+                    self.memory.set_register(procedure);
                 }
 
                 self.primitive_set
@@ -293,8 +579,10 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
     }
 
     // (code . environment)
-    const fn procedure(&self) -> Cons {
-        self.memory.car(self.operand_cons()).assume_cons()
+    fn procedure(&self) -> Option<Cons> {
+        let procedure = self.memory.car(self.operand_cons()).to_cons()?;
+
+        (self.memory.cdr(procedure).to_cons()?.tag() == Type::Procedure as _).then_some(procedure)
     }
 
     // (parameter-count . instruction-list) | primitive-id
@@ -335,6 +623,15 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
         }
     }
 
+    #[cfg(feature = "profile")]
+    fn profile_garbage_collection(&self) {
+        if let Some(profiler) = &self.profiler {
+            profiler
+                .borrow_mut()
+                .profile_garbage_collection(&self.memory);
+        }
+    }
+
     #[cfg(feature = "profile")]
     fn profile_event(&self, name: &str) {
         if let Some(profiler) = &self.profiler {
@@ -342,7 +639,26 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
         }
     }
 
+    /// Returns a heap image of a virtual machine.
+    ///
+    /// See [`Memory::image`] for its format.
+    pub fn image(&self) -> impl Iterator<Item = u8> + '_ {
+        self.memory.image()
+    }
+
+    /// Restores a virtual machine from a heap image.
+    ///
+    /// This can be used in place of [`Vm::initialize`] to start a virtual
+    /// machine from a state captured by [`Vm::image`].
+    pub fn restore(&mut self, image: impl IntoIterator<Item = u8>) -> Result<(), Error> {
+        self.memory.restore(image)
+    }
+
     /// Initializes a virtual machine with bytecodes of a program.
+    ///
+    /// Bytecodes are verified before execution. Ones with incompatible headers
+    /// or malformed bodies, such as truncated integers, out-of-range tags or
+    /// share indices, and invalid instruction graphs, are rejected with errors.
     pub fn initialize<'b>(
         &mut self,
         input: impl IntoIterator<Item = u8> + 'b,
@@ -350,19 +666,50 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
         profile_event!(self, "initialization_start");
         profile_event!(self, "decode_start");
 
-        let program = self.decode_ribs(&mut input.into_iter())?;
-        self.memory
-            .set_false(self.memory.car(program).assume_cons());
-        self.memory.set_code(self.memory.cdr(program).assume_cons());
+        let mut input = input.into_iter();
+        Header::decode(&mut input)?.check(self.fingerprint.as_ref())?;
+        let program = self.decode_ribs(&mut input)?;
+        let r#false = self
+            .memory
+            .car(program)
+            .to_cons()
+            .ok_or(Error::IllegalProgram)?;
+
+        if !self.memory.car(r#false).is_cons() || !self.memory.cdr(r#false).is_cons() {
+            return Err(Error::IllegalProgram);
+        }
+
+        self.memory.set_false(r#false);
+        let code = self
+            .memory
+            .cdr(program)
+            .to_cons()
+            .ok_or(Error::IllegalProgram)?;
 
         profile_event!(self, "decode_end");
+        profile_event!(self, "verification_start");
+
+        self.memory.verify_code(code)?;
+        self.memory.set_code(code);
+
+        profile_event!(self, "verification_end");
+
+        // Keep a program alive in a continuation of a top-level frame so that we
+        // can call procedures defined in the program after its execution.
+        let stack = if self.retain_program {
+            self.memory
+                .cons(self.memory.code().into(), self.memory.null())?
+        } else {
+            self.memory.null()
+        };
+        self.memory.set_register(stack);
 
         // Initialize an implicit top-level frame.
         let codes = self
             .memory
             .cons(Number::default().into(), self.memory.null())?
             .into();
-        let continuation = self.memory.cons(codes, self.memory.null())?.into();
+        let continuation = self.memory.cons(codes, self.memory.register())?.into();
         let stack = self.memory.allocate(
             continuation,
             self.memory.null().set_tag(StackSlot::Frame as _).into(),
@@ -375,26 +722,39 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
         Ok(())
     }
 
+    // We track sizes of a stack and a share dictionary during decoding so that
+    // malformed bytecodes never let us read beyond them.
     fn decode_ribs(&mut self, input: &mut impl Iterator<Item = u8>) -> Result<Cons, Error> {
+        let mut stack_size = 0usize;
+        let mut dictionary_size = 0usize;
+
         while let Some(head) = input.next() {
             if head & 1 == 0 {
-                let cdr = self.memory.pop();
+                let [cdr] = self.pop_decoded(&mut stack_size)?;
                 let cons = self
                     .memory
                     .allocate(Number::from_i64((head >> 1) as _).into(), cdr)?;
                 self.memory.push(cons.into())?;
+                stack_size += 1;
             } else if head & 0b10 == 0 {
                 let head = head >> 2;
 
                 if head == 0 {
+                    if stack_size == 0 {
+                        return Err(Error::StackUnderflow);
+                    }
+
                     let value = self.memory.top();
                     let cons = self.memory.cons(value, self.memory.code())?;
                     self.memory.set_code(cons);
+                    dictionary_size += 1;
                 } else {
                     let integer = Self::decode_integer_tail(input, head - 1, SHARE_BASE)?;
                     let index = integer >> 1;
 
-                    if index > 0 {
+                    if index >= dictionary_size as u128 {
+                        return Err(Error::IllegalShareIndex);
+                    } else if index > 0 {
                         let cons = self.memory.tail(self.memory.code(), index as usize - 1);
                         let head = self.memory.cdr(cons).assume_cons();
                         let tail = self.memory.cdr(head);
@@ -408,43 +768,72 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
                     if integer & 1 == 0 {
                         self.memory
                             .set_code(self.memory.cdr(self.memory.code()).assume_cons());
+                        dictionary_size -= 1;
                     }
 
                     self.memory.push(value)?;
+                    stack_size += 1;
                 }
             } else if head & 0b100 == 0 {
-                let cdr = self.memory.pop();
-                let car = self.memory.pop();
+                let [car, cdr] = self.pop_decoded(&mut stack_size)?;
                 let r#type = Self::decode_integer_tail(input, head >> 3, TAG_BASE)?;
+
+                // The maximum tag is reserved for an internal use.
+                if r#type >= Tag::MAX as _ {
+                    return Err(Error::IllegalTag);
+                }
+
                 let cons = self.memory.allocate(car, cdr.set_tag(r#type as _))?;
                 self.memory.push(cons.into())?;
+                stack_size += 1;
             } else {
-                self.memory.push(
-                    Self::decode_number(Self::decode_integer_tail(input, head >> 3, NUMBER_BASE)?)
-                        .into(),
-                )?;
+                let number =
+                    self.decode_number(Self::decode_integer_tail(input, head >> 3, NUMBER_BASE)?)?;
+                self.memory.push(number)?;
+                stack_size += 1;
             }
         }
 
-        self.memory.pop().to_cons().ok_or(Error::BytecodeEnd)
+        match stack_size {
+            0 => Err(Error::BytecodeEnd),
+            1 => self.memory.pop().to_cons().ok_or(Error::IllegalProgram),
+            _ => Err(Error::IllegalProgram),
+        }
     }
 
-    fn decode_number(integer: u128) -> Number {
-        if integer & 1 == 0 {
-            Number::from_i64((integer >> 1) as _)
+    fn pop_decoded<const M: usize>(&mut self, stack_size: &mut usize) -> Result<[Value; M], Error> {
+        *stack_size = stack_size.checked_sub(M).ok_or(Error::StackUnderflow)?;
+
+        Ok(self.memory.pop_many())
+    }
+
+    #[cfg_attr(not(feature = "flonum"), allow(clippy::unused_self))]
+    fn decode_number(&mut self, integer: u128) -> Result<Value, Error> {
+        Ok(if integer & 1 == 0 {
+            Number::from_i64((integer >> 1) as _).into()
         } else if integer & 0b10 == 0 {
-            Number::from_i64(-((integer >> 2) as i64))
+            Number::from_i64(-((integer >> 2) as i64)).into()
         } else {
             let integer = integer >> 2;
-            let mantissa = if integer % 2 == 0 { 1.0 } else { -1.0 } * (integer >> 12) as f64;
-            let exponent = ((integer >> 1) % (1 << 11)) as isize - 1023;
-
-            Number::from_f64(if exponent < 0 {
-                mantissa / (1u64 << exponent.abs()) as f64
+            let mantissa = if integer & 1 == 0 { 1.0 } else { -1.0 } * (integer >> 12) as f64;
+            let exponent = ((integer >> 1) % (1 << 11)) as i32 - 1023;
+            // Scale a mantissa in two steps as an exponent can be out of a normal
+            // range of floating-point numbers.
+            let number = mantissa
+                * Self::power_of_two(exponent / 2)
+                * Self::power_of_two(exponent - exponent / 2);
+
+            feature!(if ("flonum") {
+                self.memory.allocate_flonum(number)?.into()
             } else {
-                mantissa * (1u64 << exponent) as f64
+                Number::from_f64(number).into()
             })
-        }
+        })
+    }
+
+    // Returns 2 to the power of an exponent in a normal range of `f64`.
+    const fn power_of_two(exponent: i32) -> f64 {
+        f64::from_bits(((exponent + 1023) as u64) << 52)
     }
 
     fn decode_integer_tail(
@@ -456,8 +845,16 @@ impl<'a, T: PrimitiveSet> Vm<'a, T> {
 
         while x & 1 != 0 {
             x = input.next().ok_or(Error::BytecodeEnd)?;
-            y += (x as u128 >> 1) * base;
-            base *= INTEGER_BASE;
+            y = (x as u128 >> 1)
+                .checked_mul(base)
+                .and_then(|z| z.checked_add(y))
+                .ok_or(Error::IllegalInteger)?;
+
+            if x & 1 != 0 {
+                base = base
+                    .checked_mul(INTEGER_BASE)
+                    .ok_or(Error::IllegalInteger)?;
+            }
         }
 
         Ok(y)
@@ -0,0 +1,507 @@
+#![expect(missing_docs)]
+#![no_std]
+
+use stak_device::FixedBufferDevice;
+use stak_file::VoidFileSystem;
+use stak_macro::compile_r7rs;
+use stak_process_context::VoidProcessContext;
+use stak_r7rs::SmallPrimitiveSet;
+use stak_time::VoidClock;
+use stak_vm::{Status, Value, Vm};
+
+const HEAP_SIZE: usize = 1 << 16;
+const BUFFER_SIZE: usize = 1 << 10;
+
+fn create_vm(
+    heap: &mut [Value],
+) -> Vm<
+    SmallPrimitiveSet<
+        FixedBufferDevice<BUFFER_SIZE, 0>,
+        VoidFileSystem,
+        VoidProcessContext,
+        VoidClock,
+    >,
+> {
+    Vm::new(
+        heap,
+        SmallPrimitiveSet::new(
+            FixedBufferDevice::<BUFFER_SIZE, 0>::new(&[]),
+            VoidFileSystem::new(),
+            VoidProcessContext::new(),
+            VoidClock::new(),
+        ),
+    )
+    .unwrap()
+}
+
+mod fuel {
+    use super::*;
+
+    #[test]
+    fn run_out_of_fuel() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap);
+
+        const MODULE: &[u8] = compile_r7rs!(
+            r#"
+            (import (scheme base))
+
+            (let loop () (loop))
+            "#
+        );
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+
+        assert_eq!(vm.run_with_fuel(1 << 10).unwrap(), Status::OutOfFuel);
+        assert_eq!(vm.run_with_fuel(1 << 10).unwrap(), Status::OutOfFuel);
+    }
+
+    #[test]
+    fn resume() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap);
+
+        const MODULE: &[u8] = compile_r7rs!(
+            r#"
+            (import (scheme base) (scheme write))
+
+            (do ((x 0 (+ x 1))) ((= x 3)) (write x))
+            "#
+        );
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+
+        while vm.run_with_fuel(1).unwrap() == Status::OutOfFuel {}
+
+        assert_eq!(vm.primitive_set().device().output(), b"012");
+    }
+
+    #[test]
+    fn finish() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap);
+
+        const MODULE: &[u8] = compile_r7rs!("");
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+        vm.run().unwrap();
+
+        assert_eq!(vm.run_with_fuel(0).unwrap(), Status::Finished);
+    }
+}
+
+mod suspension {
+    use super::*;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use stak_r7rs::SmallError;
+    use stak_vm::{Memory, PrimitiveSet};
+
+    const SUSPEND_PRIMITIVE: usize = 500;
+
+    struct SuspendPrimitiveSet {
+        small: SmallPrimitiveSet<
+            FixedBufferDevice<'static, BUFFER_SIZE, 0>,
+            VoidFileSystem,
+            VoidProcessContext,
+            VoidClock,
+        >,
+    }
+
+    impl PrimitiveSet for SuspendPrimitiveSet {
+        type Error = SmallError;
+
+        fn operate(&mut self, memory: &mut Memory, primitive: usize) -> Result<(), Self::Error> {
+            if primitive == SUSPEND_PRIMITIVE {
+                memory.suspend();
+                memory.push(memory.boolean(false).into())?;
+
+                Ok(())
+            } else {
+                self.small.operate(memory, primitive)
+            }
+        }
+    }
+
+    #[test]
+    fn suspend_by_primitive() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = Vm::new(
+            &mut heap,
+            SuspendPrimitiveSet {
+                small: SmallPrimitiveSet::new(
+                    FixedBufferDevice::new(&[]),
+                    VoidFileSystem::new(),
+                    VoidProcessContext::new(),
+                    VoidClock::new(),
+                ),
+            },
+        )
+        .unwrap();
+
+        const MODULE: &[u8] = compile_r7rs!(
+            r#"
+            (import (scheme base) (scheme write) (only (stak base) primitive))
+
+            (define suspend (primitive 500))
+
+            (write 1)
+            (suspend)
+            (write 2)
+            (suspend)
+            (write 3)
+            "#
+        );
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+
+        assert_eq!(vm.run_with_fuel(usize::MAX).unwrap(), Status::Suspended);
+        assert_eq!(vm.primitive_set().small.device().output(), b"1");
+
+        assert_eq!(vm.run().unwrap(), Status::Suspended);
+        assert!(!vm.is_finished());
+        assert_eq!(vm.primitive_set().small.device().output(), b"12");
+
+        assert_eq!(vm.run().unwrap(), Status::Finished);
+        assert!(vm.is_finished());
+        assert_eq!(vm.primitive_set().small.device().output(), b"123");
+    }
+
+    #[test]
+    fn suspend_by_interrupt() {
+        let interrupt = AtomicBool::new(false);
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_interrupt(&interrupt);
+
+        const MODULE: &[u8] = compile_r7rs!(
+            r#"
+            (import (scheme base) (scheme write))
+
+            (write 42)
+            "#
+        );
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+
+        interrupt.store(true, Ordering::Relaxed);
+
+        assert_eq!(vm.run_with_fuel(usize::MAX).unwrap(), Status::Suspended);
+        assert!(!interrupt.load(Ordering::Relaxed));
+        assert_eq!(vm.primitive_set().device().output(), b"");
+
+        assert_eq!(vm.run_with_fuel(usize::MAX).unwrap(), Status::Finished);
+        assert_eq!(vm.primitive_set().device().output(), b"42");
+    }
+}
+
+mod apply {
+    use super::*;
+    use stak_r7rs::SmallError;
+    use stak_vm::{Cons, Error, Number, PrimitiveSet, Type};
+
+    const MODULE: &[u8] = compile_r7rs!(
+        r#"
+        (import (scheme base) (scheme write))
+
+        (define (add x y)
+          (write x)
+          (+ x y))
+
+        (define (identity . xs) xs)
+        "#
+    );
+
+    #[test]
+    fn apply_procedure() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_retained_program();
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+        vm.run().unwrap();
+
+        for index in 0..3 {
+            let add = vm.memory().car(find_symbol(&vm, "add"));
+            let value = vm
+                .apply(add, |memory, _| {
+                    memory.push(Number::from_i64(index).into())?;
+                    memory.push(Number::from_i64(2).into())?;
+                    Ok(2)
+                })
+                .unwrap();
+
+            assert_eq!(value, Number::from_i64(index + 2).into());
+        }
+
+        assert!(vm.is_finished());
+        assert_eq!(vm.primitive_set().device().output(), b"012");
+    }
+
+    #[test]
+    fn apply_variadic_procedure() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_retained_program();
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+        vm.run().unwrap();
+
+        let identity = vm.memory().car(find_symbol(&vm, "identity"));
+
+        let value = vm
+            .apply(identity, |memory, _| {
+                memory.push(Number::from_i64(42).into())?;
+                Ok(1)
+            })
+            .unwrap();
+
+        let memory = vm.memory();
+
+        assert_eq!(memory.car_value(value), Number::from_i64(42).into());
+        assert_eq!(memory.cdr_value(value), memory.null().into());
+    }
+
+    #[test]
+    fn apply_after_garbage_collection() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_retained_program();
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+        vm.run().unwrap();
+        vm.memory_mut().collect_garbages(None).unwrap();
+
+        let add = vm.memory().car(find_symbol(&vm, "add"));
+
+        let value = vm
+            .apply(add, |memory, _| {
+                memory.push(Number::from_i64(1).into())?;
+                memory.push(Number::from_i64(2).into())?;
+                Ok(2)
+            })
+            .unwrap();
+
+        assert_eq!(value, Number::from_i64(3).into());
+    }
+
+    #[test]
+    fn apply_before_finish() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_retained_program();
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+
+        assert!(matches!(
+            vm.apply(Number::default().into(), |_, _| Ok(0)),
+            Err(SmallError::Vm(Error::ProgramNotFinished))
+        ));
+    }
+
+    #[test]
+    fn apply_non_procedure() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_retained_program();
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+        vm.run().unwrap();
+
+        assert!(matches!(
+            vm.apply(Number::from_i64(42).into(), |_, _| Ok(0)),
+            Err(SmallError::Vm(Error::ProcedureExpected))
+        ));
+        assert!(vm.is_finished());
+    }
+
+    #[test]
+    fn apply_after_error() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_retained_program();
+
+        vm.initialize(MODULE.iter().copied()).unwrap();
+        vm.run().unwrap();
+
+        let add = vm.memory().car(find_symbol(&vm, "add"));
+
+        assert!(matches!(
+            vm.apply(add, |memory, _| {
+                memory.push(Number::from_i64(1).into())?;
+                Ok(1)
+            }),
+            Err(SmallError::Vm(Error::ArgumentCount))
+        ));
+        assert!(vm.is_finished());
+
+        let add = vm.memory().car(find_symbol(&vm, "add"));
+
+        let value = vm
+            .apply(add, |memory, _| {
+                memory.push(Number::from_i64(1).into())?;
+                memory.push(Number::from_i64(2).into())?;
+                Ok(2)
+            })
+            .unwrap();
+
+        assert_eq!(value, Number::from_i64(3).into());
+        assert_eq!(vm.backtrace().count(), 0);
+    }
+
+    fn find_symbol<T: PrimitiveSet>(vm: &Vm<T>, name: &str) -> Cons {
+        let memory = vm.memory();
+        let mut symbols = memory.car(memory.boolean(true)).assume_cons();
+
+        // Look up a symbol table in a `car` of `#t`.
+        core::iter::from_fn(|| {
+            (symbols != memory.null()).then(|| {
+                let symbol = memory.car(symbols).assume_cons();
+                symbols = memory.cdr(symbols).assume_cons();
+                symbol
+            })
+        })
+        .find(|&cons| {
+            memory.cdr(cons).tag() == Type::Symbol as _ && {
+                let mut string = memory.cdr_value(memory.cdr(cons)).assume_cons();
+
+                name.chars().all(|character| {
+                    let matched = string != memory.null()
+                        && memory.car(string) == Number::from_i64(character as _).into();
+
+                    if matched {
+                        string = memory.cdr(string).assume_cons();
+                    }
+
+                    matched
+                }) && string == memory.null()
+            }
+        })
+        .unwrap()
+    }
+}
+
+mod verification {
+    use super::*;
+    use stak_vm::{Error, Header};
+
+    fn initialize(bytecode: &[u8]) -> Result<(), Error> {
+        let mut heap = [Default::default(); HEAP_SIZE];
+
+        create_vm(&mut heap).initialize(Header::default().encode().chain(bytecode.iter().copied()))
+    }
+
+    #[test]
+    fn reject_missing_header() {
+        let mut heap = [Default::default(); HEAP_SIZE];
+
+        assert_eq!(
+            create_vm(&mut heap).initialize([7, 7, 3, 1, 13, 3, 7, 5, 3, 3]),
+            Err(Error::IllegalMagicNumber)
+        );
+    }
+
+    #[test]
+    fn check_fingerprint() {
+        const BYTECODE: &[u8] = &[7, 7, 3, 1, 13, 3, 7, 5, 3, 3];
+        let mut heap = [Default::default(); HEAP_SIZE];
+        let mut vm = create_vm(&mut heap).with_fingerprint([1; 8]);
+
+        assert_eq!(
+            vm.initialize(
+                Header::new(Some([1; 8]))
+                    .encode()
+                    .chain(BYTECODE.iter().copied())
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            vm.initialize(
+                Header::new(Some([2; 8]))
+                    .encode()
+                    .chain(BYTECODE.iter().copied())
+            ),
+            Err(Error::IncompatibleFingerprint)
+        );
+        assert_eq!(
+            vm.initialize(Header::default().encode().chain(BYTECODE.iter().copied())),
+            Err(Error::IncompatibleFingerprint)
+        );
+    }
+
+    #[test]
+    fn initialize_valid_program() {
+        // A program of `((null . null) . (0 . null#constant))`
+        assert_eq!(initialize(&[7, 7, 3, 1, 13, 3, 7, 5, 3, 3]), Ok(()));
+    }
+
+    #[test]
+    fn reject_empty_bytecode() {
+        assert_eq!(initialize(&[]), Err(Error::BytecodeEnd));
+    }
+
+    #[test]
+    fn reject_truncated_integer() {
+        assert_eq!(initialize(&[15]), Err(Error::BytecodeEnd));
+    }
+
+    #[test]
+    fn reject_too_large_integer() {
+        assert_eq!(initialize(&[15; 32]), Err(Error::IllegalInteger));
+    }
+
+    #[test]
+    fn initialize_integer_with_last_digit_of_largest_base() {
+        assert_eq!(
+            initialize(&[&[15][..], &[1; 17], &[0, 7, 3, 1, 13, 3, 7, 5, 3, 3]].concat()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reject_stack_underflow() {
+        assert_eq!(initialize(&[0]), Err(Error::StackUnderflow));
+        assert_eq!(initialize(&[7, 3]), Err(Error::StackUnderflow));
+        assert_eq!(initialize(&[1]), Err(Error::StackUnderflow));
+    }
+
+    #[test]
+    fn reject_illegal_share_index() {
+        assert_eq!(initialize(&[5]), Err(Error::IllegalShareIndex));
+        assert_eq!(initialize(&[7, 1, 21]), Err(Error::IllegalShareIndex));
+    }
+
+    #[test]
+    fn reject_illegal_tag() {
+        assert_eq!(initialize(&[7, 7, 251, 1, 254]), Err(Error::IllegalTag));
+    }
+
+    #[test]
+    fn reject_illegal_program() {
+        assert_eq!(initialize(&[7]), Err(Error::IllegalProgram));
+        assert_eq!(initialize(&[7, 7]), Err(Error::IllegalProgram));
+        assert_eq!(initialize(&[7, 7, 3]), Err(Error::IllegalProgram));
+    }
+
+    #[test]
+    fn initialize_stack_index() {
+        // A program of `((null . null) . (0 . null#get))`
+        assert_eq!(initialize(&[7, 7, 3, 1, 13, 3, 7, 5, 19, 3]), Ok(()));
+    }
+
+    #[test]
+    fn reject_negative_stack_index() {
+        assert_eq!(
+            initialize(&[7, 7, 3, 1, 13, 3, 87, 5, 19, 3]),
+            Err(Error::IllegalInstruction)
+        );
+    }
+
+    #[test]
+    fn reject_non_symbol_operand() {
+        assert_eq!(
+            initialize(&[7, 7, 3, 1, 13, 3, 13, 5, 19, 3]),
+            Err(Error::IllegalInstruction)
+        );
+    }
+
+    #[test]
+    fn reject_illegal_instruction() {
+        assert_eq!(
+            initialize(&[7, 7, 3, 1, 5, 3, 7, 7, 7, 3, 51, 3]),
+            Err(Error::IllegalInstruction)
+        );
+    }
+}